@@ -0,0 +1,23 @@
+use aoc_common_lib::error::Result;
+use aoc_common_lib::solution::Solution;
+
+#[derive(Debug, Default)]
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, lines: &Self::Input) -> Result<usize> {
+        Ok(lines.len())
+    }
+
+    fn part2(&self, lines: &Self::Input) -> Result<usize> {
+        Ok(lines.len())
+    }
+}
@@ -1,44 +1,24 @@
 /*
 Basic rust bin with runtime error and arg parsing
 */
-use std::path::Path;
-use std::{ env, error::Error };
+use std::env;
 
-use aoc_common_lib::error::RuntimeError;
-use aoc_common_lib::utility::read_lines;
-
-// Override the alias to use `Box<error::Error>`.
-type Result<T> = std::result::Result<T, Box<dyn Error>>;
-
-fn parse_spam(input_file_path: &str) -> Result<()> {
-    let input_file = Path::new(input_file_path);
-    if !input_file.exists() {
-        let error_message = format!("Path {} does not appear to exist", input_file_path);
-        return Err(Box::new(RuntimeError::new(error_message)));
-    }
-    let mut objs: Vec<Option<()>> = Vec::new();
-    if let Ok(lines) = read_lines(input_file) {
-        for line in lines {
-            match line {
-                Ok(line) => {
-                    line;
-                }
-                Err(err) => {
-                    return Err(Box::new(err));
-                }
-            }
-        }
-    }
-    Ok(())
-}
+use advent_of_code_template::Puzzle;
+use aoc_common_lib::error::{ AocError, OnLine, Result };
+use aoc_common_lib::input::Input;
+use aoc_common_lib::solution::Solution;
 
 fn main() -> Result<()> {
     let args: Vec<String> = env::args().collect();
     if args.len() < 2 {
-        return Err(Box::new(RuntimeError::new(String::from("Must provide input file path"))));
+        return Err(AocError::usage("Must provide input file path"));
     }
     let input_path = &args[1];
-    let results = parse_spam(input_path);
+    let input = Input::open(input_path)?;
+    let input = Puzzle.parse(input.as_str()).in_file(input.name())?;
+
+    println!("Part 1: {}", Puzzle.part1(&input)?);
+    println!("Part 2: {}", Puzzle.part2(&input)?);
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,80 @@
+/*
+Single entry point that runs any registered day through the shared `Solution` trait
+*/
+use std::time::Instant;
+
+use aoc_common_lib::error::{ AocError, OnLine, Result };
+use aoc_common_lib::input::Input;
+use clap::{ Parser, Subcommand };
+
+mod registry;
+
+use registry::{ default_input_path, find_day, DAYS };
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Run Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a day's solution against an input file
+    Run {
+        /// Day number to run
+        #[arg(short, long)]
+        day: u8,
+        /// Part to run, both parts are run when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input, defaults to `day-NN/input_advent_challenge.txt`. `-`
+        /// reads stdin and `.gz` files are decompressed
+        #[arg(short, long)]
+        input: Option<String>,
+    },
+    /// List the registered days
+    List,
+}
+
+fn run(day_number: u8, part: Option<u8>, input_path: Option<String>) -> Result<()> {
+    let day = match find_day(day_number) {
+        Some(day) => day,
+        None => {
+            return Err(AocError::Usage(format!("Day {} is not registered", day_number)));
+        }
+    };
+    let input = Input::open(input_path.unwrap_or_else(|| default_input_path(day_number)))?;
+
+    let started = Instant::now();
+    let solution = day.solution.parse_input(input.as_str()).in_file(input.name())?;
+    println!("Day {} - {} (parsed in {:?})", day.number, day.title, started.elapsed());
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    for part in parts {
+        let started = Instant::now();
+        let answer = match part {
+            1 => solution.part1().in_file(input.name())?,
+            _ => solution.part2().in_file(input.name())?,
+        };
+        println!("Part {}: {} ({:?})", part, answer, started.elapsed());
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run { day, part, input } => run(day, part, input),
+        Command::List => {
+            for day in DAYS {
+                println!("{:>2} {}", day.number, day.title);
+            }
+            Ok(())
+        }
+    }
+}
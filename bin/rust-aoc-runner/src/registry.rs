@@ -0,0 +1,35 @@
+use aoc_common_lib::solution::DynSolution;
+
+use advent_day_1::CalorieCounting;
+use advent_day_2::RockPaperScissors;
+use advent_day_3::RucksackReorganization;
+use advent_day_4::CampCleanup;
+use advent_day_5::SupplyStacks;
+use advent_day_6::TuningTrouble;
+use advent_day_7::NoSpaceLeftOnDevice;
+
+/// A registered day: its number, puzzle title and its solution.
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub solution: &'static (dyn DynSolution + Sync),
+}
+
+pub const DAYS: &[Day] = &[
+    Day { number: 1, title: "Calorie Counting", solution: &CalorieCounting },
+    Day { number: 2, title: "Rock Paper Scissors", solution: &RockPaperScissors },
+    Day { number: 3, title: "Rucksack Reorganization", solution: &RucksackReorganization },
+    Day { number: 4, title: "Camp Cleanup", solution: &CampCleanup },
+    Day { number: 5, title: "Supply Stacks", solution: &SupplyStacks },
+    Day { number: 6, title: "Tuning Trouble", solution: &TuningTrouble },
+    Day { number: 7, title: "No Space Left On Device", solution: &NoSpaceLeftOnDevice },
+];
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// Default location of a day's challenge input relative to the repository root.
+pub fn default_input_path(number: u8) -> String {
+    format!("day-{:02}/input_advent_challenge.txt", number)
+}
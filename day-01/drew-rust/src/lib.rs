@@ -0,0 +1,75 @@
+use aoc_common_lib::error::{ OnLine, Result };
+use aoc_common_lib::solution::Solution;
+use aoc_common_lib::utility::blocks;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Elf {
+    pub id: u8,
+    pub calories: u32,
+}
+
+pub fn parse_elves(input: &str) -> Result<Vec<Elf>> {
+    let mut elves: Vec<Elf> = Vec::new();
+
+    // Each elf's calories are a block of lines separated by a blank line
+    for (id, block) in (1..).zip(blocks(input)) {
+        let mut calories: u32 = 0;
+        for (line_number, line) in block.numbered_lines() {
+            let trimmed_line = line.trim();
+            let line_calories: u32 = trimmed_line.parse().at_token(line_number, line, trimmed_line)?;
+            calories += line_calories;
+        }
+        elves.push(Elf { id, calories });
+    }
+
+    Ok(elves)
+}
+
+/// Sort by reversed calories to get descending values
+pub fn sort_by_calories(elves: &mut [Elf]) {
+    elves.sort_by_key(|elf| std::cmp::Reverse(elf.calories));
+}
+
+#[derive(Debug, Default)]
+pub struct CalorieCounting;
+
+impl Solution for CalorieCounting {
+    type Input = Vec<Elf>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut elves = parse_elves(input)?;
+        sort_by_calories(&mut elves);
+        Ok(elves)
+    }
+
+    fn part1(&self, elves: &Self::Input) -> Result<u32> {
+        Ok(elves.first().map(|elf| elf.calories).unwrap_or(0))
+    }
+
+    fn part2(&self, elves: &Self::Input) -> Result<u32> {
+        Ok(
+            elves
+                .iter()
+                .take(3)
+                .map(|elf| elf.calories)
+                .sum::<u32>()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn solves_example() {
+        let elves = CalorieCounting.parse(EXAMPLE).unwrap();
+        assert_eq!(elves.len(), 5);
+        assert_eq!(CalorieCounting.part1(&elves).unwrap(), 24000);
+        assert_eq!(CalorieCounting.part2(&elves).unwrap(), 45000);
+    }
+}
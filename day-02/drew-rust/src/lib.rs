@@ -0,0 +1,180 @@
+use std::str::FromStr;
+
+use aoc_common_lib::diagnostic::Location;
+use aoc_common_lib::error::{ OnLine, ParseError, Result };
+use aoc_common_lib::solution::Solution;
+
+#[derive(Debug, PartialEq)]
+pub enum PlayerMove {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+impl FromStr for PlayerMove {
+    type Err = ParseError;
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        match input {
+            "A" => Ok(PlayerMove::Rock),
+            "B" => Ok(PlayerMove::Paper),
+            "C" => Ok(PlayerMove::Scissors),
+            "X" => Ok(PlayerMove::Rock),
+            "Y" => Ok(PlayerMove::Paper),
+            "Z" => Ok(PlayerMove::Scissors),
+            _ => Err(ParseError::new("expected a move of A, B, C, X, Y or Z")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RoundResult {
+    Lose = 0,
+    Tie = 3,
+    Win = 6,
+}
+
+impl FromStr for RoundResult {
+    type Err = ParseError;
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        match input {
+            "X" => Ok(RoundResult::Lose),
+            "Y" => Ok(RoundResult::Tie),
+            "Z" => Ok(RoundResult::Win),
+            _ => Err(ParseError::new("expected a round result of X, Y or Z")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RoundScore {
+    pub round: u32,
+    pub player_1_move: PlayerMove,
+    pub player_1_score: u32,
+    pub player_2_move: PlayerMove,
+    pub player_2_score: u32,
+    pub player_2_ideal_move: PlayerMove,
+    pub player_2_ideal_score: u32,
+}
+
+pub fn calculate_move_score(player_1_move: &PlayerMove, player_2_move: &PlayerMove) -> u32 {
+    match player_1_move {
+        PlayerMove::Rock =>
+            match player_2_move {
+                PlayerMove::Rock => (PlayerMove::Rock as u32) + (RoundResult::Tie as u32),
+                PlayerMove::Paper => (PlayerMove::Rock as u32) + (RoundResult::Lose as u32),
+                PlayerMove::Scissors => (PlayerMove::Rock as u32) + (RoundResult::Win as u32),
+            }
+        PlayerMove::Paper =>
+            match player_2_move {
+                PlayerMove::Rock => (PlayerMove::Paper as u32) + (RoundResult::Win as u32),
+                PlayerMove::Paper => (PlayerMove::Paper as u32) + (RoundResult::Tie as u32),
+                PlayerMove::Scissors => (PlayerMove::Paper as u32) + (RoundResult::Lose as u32),
+            }
+        PlayerMove::Scissors =>
+            match player_2_move {
+                PlayerMove::Rock => (PlayerMove::Scissors as u32) + (RoundResult::Lose as u32),
+                PlayerMove::Paper => (PlayerMove::Scissors as u32) + (RoundResult::Win as u32),
+                PlayerMove::Scissors => (PlayerMove::Scissors as u32) + (RoundResult::Tie as u32),
+            }
+    }
+}
+
+pub fn calculate_ideal_move(player_1_move: &PlayerMove, player_2_ideal_result: &RoundResult) -> PlayerMove {
+    match player_2_ideal_result {
+        RoundResult::Lose =>
+            match player_1_move {
+                PlayerMove::Rock => PlayerMove::Scissors,
+                PlayerMove::Paper => PlayerMove::Rock,
+                PlayerMove::Scissors => PlayerMove::Paper,
+            }
+        RoundResult::Tie =>
+            match player_1_move {
+                PlayerMove::Rock => PlayerMove::Rock,
+                PlayerMove::Paper => PlayerMove::Paper,
+                PlayerMove::Scissors => PlayerMove::Scissors,
+            }
+        RoundResult::Win =>
+            match player_1_move {
+                PlayerMove::Rock => PlayerMove::Paper,
+                PlayerMove::Paper => PlayerMove::Scissors,
+                PlayerMove::Scissors => PlayerMove::Rock,
+            }
+    }
+}
+
+pub fn parse_game_rounds(input: &str) -> Result<Vec<RoundScore>> {
+    let mut round_scores = Vec::new();
+    for (round_number, line) in (1u32..).zip(input.lines()) {
+        let line_number = round_number as usize;
+        let line_parts: Vec<&str> = line.split_whitespace().collect();
+        if line_parts.len() != 2 {
+            let location = Location::line(line_number, line);
+            return Err(ParseError::new("expected two moves separated by a space").located(location).into());
+        }
+        let player_1_move = PlayerMove::from_str(line_parts[0]).at_token(line_number, line, line_parts[0])?;
+        let player_2_move = PlayerMove::from_str(line_parts[1]).at_token(line_number, line, line_parts[1])?;
+        let player_2_ideal_result = RoundResult::from_str(line_parts[1]).at_token(line_number, line, line_parts[1])?;
+        let player_2_ideal_move: PlayerMove = calculate_ideal_move(&player_1_move, &player_2_ideal_result);
+
+        let player_1_score = calculate_move_score(&player_1_move, &player_2_move);
+        let player_2_score = calculate_move_score(&player_2_move, &player_1_move);
+        let player_2_ideal_score: u32 = calculate_move_score(&player_2_ideal_move, &player_1_move);
+
+        round_scores.push(RoundScore {
+            round: round_number,
+            player_1_move,
+            player_1_score,
+            player_2_move,
+            player_2_score,
+            player_2_ideal_move,
+            player_2_ideal_score,
+        });
+    }
+
+    Ok(round_scores)
+}
+
+#[derive(Debug, Default)]
+pub struct RockPaperScissors;
+
+impl Solution for RockPaperScissors {
+    type Input = Vec<RoundScore>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_game_rounds(input)
+    }
+
+    fn part1(&self, round_scores: &Self::Input) -> Result<u32> {
+        Ok(
+            round_scores
+                .iter()
+                .map(|round_score| round_score.player_2_score)
+                .sum::<u32>()
+        )
+    }
+
+    fn part2(&self, round_scores: &Self::Input) -> Result<u32> {
+        Ok(
+            round_scores
+                .iter()
+                .map(|round_score| round_score.player_2_ideal_score)
+                .sum::<u32>()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn solves_example() {
+        let round_scores = RockPaperScissors.parse(EXAMPLE).unwrap();
+        assert_eq!(RockPaperScissors.part1(&round_scores).unwrap(), 15);
+        assert_eq!(RockPaperScissors.part2(&round_scores).unwrap(), 12);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{ collections::BTreeMap, fmt::{ self, Display }, hash::Hash, iter };
+
+use aoc_common_lib::diagnostic::Location;
+use aoc_common_lib::error::{ AocError, ParseError, Result };
+use aoc_common_lib::solution::Solution;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SupplyItem {
+    pub id: char,
+    pub count: usize,
+}
+
+impl Hash for SupplyItem {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl PartialEq for SupplyItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for SupplyItem {}
+
+impl Display for SupplyItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("{} ({}: {})", self.get_priority(), self.id, self.count))
+    }
+}
+
+impl SupplyItem {
+    pub fn new(id: char) -> Self {
+        SupplyItem { id, count: 1 }
+    }
+
+    pub fn increment(&mut self) -> &mut Self {
+        self.count += 1;
+        self
+    }
+
+    pub fn get_priority(self) -> u8 {
+        determine_priority(&self.id)
+    }
+}
+
+pub fn determine_priority(id: &char) -> u8 {
+    match id {
+        // a = 97, z = 122 => 1..=26
+        'a'..='z' => (*id as u8) - 96,
+        // A = 65, Z = 90 => 27..=52
+        'A'..='Z' => (*id as u8) - 38,
+        _ => 0u8,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RucksackCompartment {
+    pub raw_contents: String,
+    pub contents: BTreeMap<u8, SupplyItem>,
+}
+
+impl RucksackCompartment {
+    pub fn new(compartment_contents: &str) -> Self {
+        let mut compartments: BTreeMap<u8, SupplyItem> = BTreeMap::new();
+
+        for c in compartment_contents.chars() {
+            let supply_item = SupplyItem::new(c);
+
+            match compartments.get_mut(&determine_priority(&c)) {
+                Some(existing_item) => {
+                    existing_item.increment();
+                }
+                None => {
+                    let key = determine_priority(&c);
+                    compartments.insert(key, supply_item);
+                }
+            }
+        }
+
+        RucksackCompartment {
+            raw_contents: String::from(compartment_contents),
+            contents: compartments,
+        }
+    }
+
+    /// The items both compartments hold, unsolvable when they share none.
+    pub fn common_items_with(&self, other: &Self) -> Result<Self> {
+        let common_items: Vec<SupplyItem> = self.contents
+            .keys()
+            .filter(|id| other.contents.contains_key(*id))
+            .map(|id| SupplyItem {
+                id: self.contents.get(id).unwrap().id,
+                count: self.contents.get(id).unwrap().count + other.contents.get(id).unwrap().count,
+            })
+            .collect();
+
+        let common_contents = common_items
+            .iter()
+            .map(|i| iter::repeat_n(i.id, i.count).collect::<String>())
+            .reduce(|this, other| format!("{}{}", this, other))
+            .ok_or_else(|| AocError::unsolvable("the rucksacks of an elf group share no item"))?;
+        Ok(Self::new(&common_contents))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rucksack {
+    pub size: usize,
+    pub compartment_1: RucksackCompartment,
+    pub compartment_2: RucksackCompartment,
+}
+
+impl Rucksack {
+    pub fn new(rucksack_contents: &str) -> Self {
+        Rucksack {
+            size: rucksack_contents.len(),
+            compartment_1: RucksackCompartment::new(&rucksack_contents[0..rucksack_contents.len() / 2]),
+            compartment_2: RucksackCompartment::new(&rucksack_contents[rucksack_contents.len() / 2..]),
+        }
+    }
+
+    /// Validate a line of rucksack contents before splitting it into its two
+    /// compartments.
+    pub fn parse(line_number: usize, line: &str) -> Result<Self> {
+        if let Some((column, _)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            let location = Location::new(line_number, line, column + 1, 1);
+            return Err(ParseError::new("expected a supply item between a-z or A-Z").located(location).into());
+        }
+        if !line.len().is_multiple_of(2) {
+            let location = Location::line(line_number, line);
+            return Err(ParseError::new("expected an even number of supply items").located(location).into());
+        }
+        Ok(Self::new(line))
+    }
+
+    pub fn unpack(&self) -> RucksackCompartment {
+        let merged_raw_contents = format!("{}{}", self.compartment_1.raw_contents, self.compartment_2.raw_contents);
+        RucksackCompartment::new(&merged_raw_contents)
+    }
+
+    pub fn get_common_items(&self) -> Result<Vec<SupplyItem>> {
+        let common_items: Vec<SupplyItem> = self.compartment_1.contents
+            .keys()
+            .filter(|id| self.compartment_2.contents.contains_key(*id))
+            .map(|id| SupplyItem {
+                id: self.compartment_1.contents.get(id).unwrap().id,
+                count: self.compartment_1.contents.get(id).unwrap().count +
+                self.compartment_2.contents.get(id).unwrap().count,
+            })
+            .collect();
+
+        Ok(common_items)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ElfGroup {
+    pub rucksacks: Vec<Rucksack>,
+}
+
+impl ElfGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, rucksack: Rucksack) {
+        self.rucksacks.push(rucksack);
+    }
+
+    pub fn get_common_items(&self) -> Result<Vec<SupplyItem>> {
+        let unpacked_rucksacks: Vec<RucksackCompartment> = self.rucksacks
+            .iter()
+            .map(|r| r.unpack())
+            .collect();
+
+        if unpacked_rucksacks.len() < 2 {
+            return Err(AocError::unsolvable("an elf group needs at least two rucksacks to share an item"));
+        }
+
+        let mut unpacked_common_items: RucksackCompartment = unpacked_rucksacks[0].common_items_with(
+            &unpacked_rucksacks[1]
+        )?;
+        for unpacked_rucksack in unpacked_rucksacks[2..].iter() {
+            unpacked_common_items = unpacked_common_items.common_items_with(unpacked_rucksack)?;
+        }
+
+        Ok(
+            unpacked_common_items.contents
+                .iter()
+                .map(|entry| entry.1.to_owned())
+                .collect()
+        )
+    }
+
+    pub fn size(&self) -> usize {
+        self.rucksacks.len()
+    }
+}
+
+pub fn parse_elf_groups(input: &str, group_size: usize) -> Result<Vec<ElfGroup>> {
+    let mut elf_groups: Vec<ElfGroup> = Vec::new();
+    let mut current_elf_group = ElfGroup::new();
+    for (line_number, line) in (1..).zip(input.lines()) {
+        if current_elf_group.size() >= group_size {
+            elf_groups.push(current_elf_group);
+            current_elf_group = ElfGroup::new();
+        }
+        current_elf_group.add(Rucksack::parse(line_number, line)?);
+    }
+    // Capture the last group
+    elf_groups.push(current_elf_group);
+
+    Ok(elf_groups)
+}
+
+pub fn sum_rucksack_priorities(elf_groups: &[ElfGroup]) -> Result<u32> {
+    let mut overlap_points: u32 = 0;
+    for elf_group in elf_groups.iter() {
+        for rucksack in elf_group.rucksacks.iter() {
+            for supply_item in rucksack.get_common_items()?.iter() {
+                overlap_points += supply_item.get_priority() as u32;
+            }
+        }
+    }
+    Ok(overlap_points)
+}
+
+pub fn sum_elf_group_priorities(elf_groups: &[ElfGroup]) -> Result<u32> {
+    let mut elf_group_points: u32 = 0;
+    for elf_group in elf_groups.iter() {
+        for supply_item in elf_group.get_common_items()? {
+            elf_group_points += supply_item.get_priority() as u32;
+        }
+    }
+    Ok(elf_group_points)
+}
+
+#[derive(Debug, Default)]
+pub struct RucksackReorganization;
+
+impl Solution for RucksackReorganization {
+    type Input = Vec<ElfGroup>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_elf_groups(input, 3)
+    }
+
+    fn part1(&self, elf_groups: &Self::Input) -> Result<u32> {
+        sum_rucksack_priorities(elf_groups)
+    }
+
+    fn part2(&self, elf_groups: &Self::Input) -> Result<u32> {
+        sum_elf_group_priorities(elf_groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn solves_example() {
+        let elf_groups = RucksackReorganization.parse(EXAMPLE).unwrap();
+        assert_eq!(RucksackReorganization.part1(&elf_groups).unwrap(), 157);
+        assert_eq!(RucksackReorganization.part2(&elf_groups).unwrap(), 70);
+    }
+
+    #[test]
+    fn rejects_groups_sharing_no_item() {
+        let elf_groups = RucksackReorganization.parse("abca\ncdcd\nefef").unwrap();
+        let err = RucksackReorganization.part2(&elf_groups).unwrap_err();
+        assert_eq!(format!("{}", err), "Unsolvable input: the rucksacks of an elf group share no item");
+    }
+}
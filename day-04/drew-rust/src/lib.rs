@@ -0,0 +1,207 @@
+use std::fmt::{ self, Display };
+
+use aoc_common_lib::diagnostic::Location;
+use aoc_common_lib::error::{ OnLine, ParseError, Result };
+use aoc_common_lib::solution::Solution;
+
+pub struct SectionAssignment {
+    pub range_start: u32,
+    pub range_end: u32,
+}
+
+impl SectionAssignment {
+    /// Parse `input`, a slice of the line `line` so that errors can point at it.
+    pub fn parse(line_number: usize, line: &str, input: &str) -> Result<Self> {
+        match input.split_once('-') {
+            Some(parts) => {
+                let range_start = parts.0.parse::<u32>().at_token(line_number, line, parts.0)?;
+                let range_end = parts.1.parse::<u32>().at_token(line_number, line, parts.1)?;
+                Ok(SectionAssignment {
+                    range_start,
+                    range_end,
+                })
+            }
+            None => {
+                let location = Location::token(line_number, line, input);
+                Err(ParseError::new("expected a section range such as 2-4").located(location).into())
+            }
+        }
+    }
+}
+
+impl Display for SectionAssignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("{}-{}", self.range_start, self.range_end))
+    }
+}
+
+impl PartialEq for SectionAssignment {
+    fn eq(&self, other: &Self) -> bool {
+        self.range_start == other.range_start && self.range_end == other.range_end
+    }
+}
+
+pub enum AssignmentOverlap {
+    NoOverlap,
+    LeftContainsRight,
+    Overlap,
+    RightContainsLeft,
+    CompleteOverlap,
+}
+
+const NO_OVERLAP: &str = "<  L > < R  >";
+const LEFT_CONTAINS_RIGHT: &str = "<  L   < R >>";
+const OVERLAP: &str = "<  L < > R  >";
+const RIGHT_CONTAINS_LEFT: &str = "<< L >   R  >";
+const COMPLETE_OVERLAP: &str = "<  L     R  >";
+
+impl AssignmentOverlap {
+    /// This method assumes that the ordering of the start values is from least to greatest for the left and right assignment inputs
+    pub fn determine_overlap(
+        left_assignment: &SectionAssignment,
+        right_assignment: &SectionAssignment
+    ) -> AssignmentOverlap {
+        if left_assignment == right_assignment {
+            Self::CompleteOverlap
+        } else if
+            left_assignment.range_start <= right_assignment.range_start &&
+            left_assignment.range_end >= right_assignment.range_end
+        {
+            Self::LeftContainsRight
+        } else if
+            right_assignment.range_start <= left_assignment.range_start &&
+            right_assignment.range_end >= left_assignment.range_end
+        {
+            Self::RightContainsLeft
+        } else if left_assignment.range_end >= right_assignment.range_start {
+            Self::Overlap
+        } else {
+            Self::NoOverlap
+        }
+    }
+
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            AssignmentOverlap::NoOverlap => NO_OVERLAP,
+            AssignmentOverlap::LeftContainsRight => LEFT_CONTAINS_RIGHT,
+            AssignmentOverlap::Overlap => OVERLAP,
+            AssignmentOverlap::RightContainsLeft => RIGHT_CONTAINS_LEFT,
+            AssignmentOverlap::CompleteOverlap => COMPLETE_OVERLAP,
+        }
+    }
+}
+
+impl Display for AssignmentOverlap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("{}", self.glyph()))
+    }
+}
+
+pub struct AssignmentPair {
+    pub left_assignment: SectionAssignment,
+    pub right_assignment: SectionAssignment,
+    pub assignment_overlap: AssignmentOverlap,
+}
+
+impl AssignmentPair {
+    pub fn parse(line_number: usize, line: &str) -> Result<Self> {
+        match line.split_once(',') {
+            Some(parts) => {
+                let left_assignment = SectionAssignment::parse(line_number, line, parts.0)?;
+                let right_assignment = SectionAssignment::parse(line_number, line, parts.1)?;
+
+                let assignment_overlap = if left_assignment.range_start <= right_assignment.range_start {
+                    AssignmentOverlap::determine_overlap(&left_assignment, &right_assignment)
+                } else {
+                    AssignmentOverlap::determine_overlap(&right_assignment, &left_assignment)
+                };
+
+                Ok(AssignmentPair {
+                    left_assignment,
+                    right_assignment,
+                    assignment_overlap,
+                })
+            }
+            None => {
+                let location = Location::line(line_number, line);
+                Err(ParseError::new("expected two comma separated section ranges").located(location).into())
+            }
+        }
+    }
+
+    pub fn has_overlap(&self) -> bool {
+        !matches!(self.assignment_overlap, AssignmentOverlap::NoOverlap)
+    }
+
+    pub fn has_fully_contains(&self) -> bool {
+        matches!(
+            self.assignment_overlap,
+            AssignmentOverlap::LeftContainsRight |
+                AssignmentOverlap::RightContainsLeft |
+                AssignmentOverlap::CompleteOverlap
+        )
+    }
+}
+
+impl Display for AssignmentPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("{},{}  {}", self.left_assignment, self.right_assignment, self.assignment_overlap))
+    }
+}
+
+pub fn parse_assignments(input: &str) -> Result<Vec<AssignmentPair>> {
+    let mut assignment_pairs: Vec<AssignmentPair> = Vec::new();
+    for (line_number, line) in (1..).zip(input.lines()) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        assignment_pairs.push(AssignmentPair::parse(line_number, line)?);
+    }
+
+    Ok(assignment_pairs)
+}
+
+#[derive(Debug, Default)]
+pub struct CampCleanup;
+
+impl Solution for CampCleanup {
+    type Input = Vec<AssignmentPair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_assignments(input)
+    }
+
+    fn part1(&self, assignments: &Self::Input) -> Result<usize> {
+        Ok(
+            assignments
+                .iter()
+                .filter(|assignment| assignment.has_fully_contains())
+                .count()
+        )
+    }
+
+    fn part2(&self, assignments: &Self::Input) -> Result<usize> {
+        Ok(
+            assignments
+                .iter()
+                .filter(|assignment| assignment.has_overlap())
+                .count()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn solves_example() {
+        let assignments = CampCleanup.parse(EXAMPLE).unwrap();
+        assert_eq!(CampCleanup.part1(&assignments).unwrap(), 2);
+        assert_eq!(CampCleanup.part2(&assignments).unwrap(), 4);
+    }
+}
@@ -0,0 +1,36 @@
+use advent_day_5::crane::CrateMover9001;
+use advent_day_5::generate::{ generate_manifest, GeneratorConfig };
+use advent_day_5::parser::parse_cargo_bay_and_move_commands;
+use advent_day_5::top_crates_after;
+use advent_day_5::writer::to_manifest;
+use criterion::{ criterion_group, criterion_main, Criterion, Throughput };
+
+/// A manifest a few megabytes long, far beyond the challenge input.
+fn large_manifest() -> String {
+    let config = GeneratorConfig {
+        stack_count: 9,
+        max_height: 50,
+        move_count: 100_000,
+        max_move_size: 20,
+        ..GeneratorConfig::default()
+    };
+    let (cargo_bay, move_commands) = generate_manifest(&config, 5).unwrap();
+    to_manifest(&cargo_bay, &move_commands)
+}
+
+fn bench_parser(c: &mut Criterion) {
+    let manifest = large_manifest();
+    let (cargo_bay, move_commands) = parse_cargo_bay_and_move_commands(&manifest).unwrap();
+
+    let mut group = c.benchmark_group("large manifest");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(manifest.len() as u64));
+    group.bench_function("parse", |b| b.iter(|| parse_cargo_bay_and_move_commands(&manifest).unwrap()));
+    group.bench_function("apply", |b| {
+        b.iter(|| top_crates_after(&cargo_bay, &move_commands, &CrateMover9001).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parser);
+criterion_main!(benches);
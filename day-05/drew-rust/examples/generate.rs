@@ -0,0 +1,54 @@
+//! Print a generated cargo manifest, e.g.
+//!
+//! ```text
+//! cargo run --example generate -- --seed 7 --stacks 9 --moves 1000000 > large.txt
+//! ```
+
+use advent_day_5::generate::{ generate_manifest, GeneratorConfig };
+use advent_day_5::writer::write_manifest;
+use aoc_common_lib::error::Result;
+use clap::Parser;
+use std::io::{ self, Write };
+
+#[derive(Parser)]
+#[command(about = "Generate a random cargo manifest")]
+struct Cli {
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    #[arg(long, default_value_t = 9)]
+    stacks: usize,
+    #[arg(long, default_value_t = 0)]
+    min_height: usize,
+    #[arg(long, default_value_t = 8)]
+    max_height: usize,
+    /// Letters to label crates with
+    #[arg(long, default_value = "ABCDEFGHIJKLMNOPQRSTUVWXYZ")]
+    alphabet: String,
+    /// Characters in each crate label
+    #[arg(long, default_value_t = 1)]
+    label_length: usize,
+    #[arg(long, default_value_t = 500)]
+    moves: usize,
+    /// Most crates a single move may move
+    #[arg(long, default_value_t = 10)]
+    max_move_size: usize,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let config = GeneratorConfig {
+        stack_count: cli.stacks,
+        min_height: cli.min_height,
+        max_height: cli.max_height,
+        alphabet: cli.alphabet.chars().collect(),
+        label_length: cli.label_length,
+        move_count: cli.moves,
+        max_move_size: cli.max_move_size,
+    };
+    let (cargo_bay, move_commands) = generate_manifest(&config, cli.seed)?;
+
+    let mut manifest = String::new();
+    let _ = write_manifest(&mut manifest, &cargo_bay, &move_commands);
+    io::stdout().lock().write_all(manifest.as_bytes())?;
+    Ok(())
+}
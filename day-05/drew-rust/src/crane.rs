@@ -0,0 +1,125 @@
+//! Crane models. A crane decides how the crates of a [`MoveCommand`] are
+//! lifted: crates lifted together keep their order, crates lifted one after
+//! another end up reversed.
+//!
+//! [`MoveCommand`]: crate::model::MoveCommand
+
+use aoc_common_lib::error::{ AocError, Result };
+
+/// Names accepted by [`crane_by_name`], for usage messages.
+pub const CRANE_NAMES: &str = "`9000`, `9001` or `capacity:K` to lift at most K crates at once";
+
+pub trait Crane {
+    /// Name the crane is selected by.
+    fn name(&self) -> String;
+
+    /// How many crates each lift carries when moving `size` crates. The lifts
+    /// add up to `size`, which is never more than the origin stack holds.
+    fn lifts(&self, size: usize) -> Vec<usize>;
+}
+
+/// The CrateMover 9000 moves crates one at a time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        String::from("9000")
+    }
+
+    fn lifts(&self, size: usize) -> Vec<usize> {
+        vec![1; size]
+    }
+}
+
+/// The CrateMover 9001 moves a whole stack of crates at once.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        String::from("9001")
+    }
+
+    fn lifts(&self, size: usize) -> Vec<usize> {
+        if size == 0 { Vec::new() } else { vec![size] }
+    }
+}
+
+/// A crane that lifts at most `capacity` crates at once, a capacity of 1
+/// behaves like the [`CrateMover9000`].
+#[derive(Debug, Clone, Copy)]
+pub struct CapacityCrane {
+    capacity: usize,
+}
+
+impl CapacityCrane {
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(AocError::usage("A crane must be able to lift at least one crate"));
+        }
+        Ok(Self { capacity })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl Crane for CapacityCrane {
+    fn name(&self) -> String {
+        format!("capacity:{}", self.capacity)
+    }
+
+    fn lifts(&self, size: usize) -> Vec<usize> {
+        let mut lifts = vec![self.capacity; size / self.capacity];
+        if !size.is_multiple_of(self.capacity) {
+            lifts.push(size % self.capacity);
+        }
+        lifts
+    }
+}
+
+/// Select a crane by name, see [`CRANE_NAMES`].
+pub fn crane_by_name(name: &str) -> Result<Box<dyn Crane>> {
+    match name {
+        "9000" => Ok(Box::new(CrateMover9000)),
+        "9001" => Ok(Box::new(CrateMover9001)),
+        _ =>
+            match name.strip_prefix("capacity:").map(|capacity| capacity.parse::<usize>()) {
+                Some(Ok(capacity)) => Ok(Box::new(CapacityCrane::new(capacity)?)),
+                _ => Err(AocError::Usage(format!("Unknown crane `{}`, expected {}", name, CRANE_NAMES))),
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_cargo_bay_and_move_commands;
+
+    #[test]
+    fn splits_moves_into_lifts() {
+        assert_eq!(CrateMover9000.lifts(3), vec![1, 1, 1]);
+        assert_eq!(CrateMover9001.lifts(3), vec![3]);
+        assert_eq!(CrateMover9001.lifts(0), Vec::<usize>::new());
+        assert_eq!(CapacityCrane::new(2).unwrap().lifts(5), vec![2, 2, 1]);
+        assert_eq!(crane_by_name("capacity:3").unwrap().lifts(6), vec![3, 3]);
+        assert_eq!(crane_by_name("9001").unwrap().name(), "9001");
+        assert!(crane_by_name("capacity:0").is_err());
+        assert!(crane_by_name("9002").is_err());
+    }
+
+    #[test]
+    fn moves_no_more_crates_than_the_origin_holds() {
+        let manifest = "[A]    \n[B]    \n 1   2 \n\nmove 100000000000000 from 1 to 2\n";
+        let (cargo_bay, move_commands) = parse_cargo_bay_and_move_commands(manifest).unwrap();
+        let cranes: [&dyn Crane; 3] = [&CrateMover9000, &CrateMover9001, &CapacityCrane::new(3).unwrap()];
+        for crane in cranes {
+            let mut cargo_bay = cargo_bay.clone();
+            let lifts = cargo_bay.apply_lifts(&move_commands[0], crane).unwrap();
+            assert_eq!(lifts.iter().sum::<usize>(), 2);
+            assert_eq!(cargo_bay.heights(), vec![0, 2]);
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Seeded generator for cargo manifests, for stress testing and benchmarking
+//! with inputs larger than the puzzle's. The same configuration and seed always
+//! give the same manifest, and no move lifts more crates than its origin holds.
+
+use aoc_common_lib::error::{ AocError, Result };
+use rand::{ seq::SliceRandom, Rng, SeedableRng };
+use rand_chacha::ChaCha8Rng;
+
+use crate::model::{ CargoBay, CargoCrate, MoveCommand, MoveCommands };
+
+/// What the generated manifests look like. Heights are drawn uniformly from
+/// `min_height..=max_height` for each stack.
+#[derive(Debug, Clone)]
+pub struct GeneratorConfig {
+    pub stack_count: usize,
+    pub min_height: usize,
+    pub max_height: usize,
+    /// Characters crate labels are drawn from, ASCII letters and digits.
+    pub alphabet: Vec<char>,
+    /// Number of characters in each crate label.
+    pub label_length: usize,
+    pub move_count: usize,
+    /// Most crates a single move command may move.
+    pub max_move_size: usize,
+}
+
+impl Default for GeneratorConfig {
+    /// The shape of the challenge input.
+    fn default() -> Self {
+        Self {
+            stack_count: 9,
+            min_height: 0,
+            max_height: 8,
+            alphabet: ('A'..='Z').collect(),
+            label_length: 1,
+            move_count: 500,
+            max_move_size: 10,
+        }
+    }
+}
+
+impl GeneratorConfig {
+    fn validate(&self) -> Result<()> {
+        if self.stack_count == 0 {
+            return Err(AocError::usage("A manifest needs at least one stack"));
+        }
+        if self.min_height > self.max_height {
+            return Err(AocError::usage("The minimum stack height is above the maximum"));
+        }
+        if self.alphabet.is_empty() || self.label_length == 0 {
+            return Err(AocError::usage("Crates need labels, the alphabet and the label length can't be empty"));
+        }
+        if !self.alphabet.iter().all(|letter| letter.is_ascii_alphanumeric()) {
+            return Err(AocError::usage("Crate labels are made of ASCII letters and digits"));
+        }
+        if self.move_count > 0 && (self.max_height == 0 || self.max_move_size == 0) {
+            return Err(AocError::usage("Moves need crates to move"));
+        }
+        Ok(())
+    }
+}
+
+/// Generate a cargo bay and a list of moves that can all be carried out in full.
+pub fn generate_manifest(config: &GeneratorConfig, seed: u64) -> Result<(CargoBay, MoveCommands)> {
+    config.validate()?;
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    let mut heights: Vec<usize> = (0..config.stack_count)
+        .map(|_| rng.gen_range(config.min_height..=config.max_height))
+        .collect();
+    // Every stack may have come out empty, moves need at least one crate
+    if config.move_count > 0 && heights.iter().all(|height| *height == 0) {
+        heights[0] = 1;
+    }
+
+    let stacks: Vec<Vec<CargoCrate>> = heights
+        .iter()
+        .map(|height| {
+            (0..*height)
+                .map(|_| {
+                    let label: String = (0..config.label_length)
+                        .map(|_| *config.alphabet.choose(&mut rng).unwrap())
+                        .collect();
+                    CargoCrate::new(&label)
+                })
+                .collect()
+        })
+        .collect();
+    let max_height = heights.iter().max().copied().unwrap_or(0);
+    let cargo_crate_rows = (0..max_height)
+        .rev()
+        .map(|layer| {
+            stacks
+                .iter()
+                .map(|stack| stack.get(layer).cloned())
+                .collect()
+        })
+        .collect();
+    let cargo_bay = CargoBay::new(config.stack_count, cargo_crate_rows);
+
+    let mut move_commands = Vec::with_capacity(config.move_count);
+    for _ in 0..config.move_count {
+        let origins: Vec<usize> = (0..config.stack_count).filter(|stack| heights[*stack] > 0).collect();
+        let origin = *origins.choose(&mut rng).unwrap();
+        let destination = if config.stack_count == 1 {
+            origin
+        } else {
+            // Draw from every stack but the origin
+            let destination = rng.gen_range(0..config.stack_count - 1);
+            if destination >= origin { destination + 1 } else { destination }
+        };
+        let size = rng.gen_range(1..=heights[origin].min(config.max_move_size));
+
+        heights[origin] -= size;
+        heights[destination] += size;
+        move_commands.push(MoveCommand::new(size, origin, destination));
+    }
+
+    Ok((cargo_bay, move_commands))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::{ CapacityCrane, CrateMover9000, CrateMover9001, Crane };
+    use crate::parser::parse_cargo_bay_and_move_commands;
+    use crate::validate::check_move_commands;
+    use crate::writer::to_manifest;
+
+    #[test]
+    fn generates_valid_manifests() {
+        let config = GeneratorConfig {
+            stack_count: 5,
+            min_height: 0,
+            max_height: 20,
+            alphabet: vec!['X', 'Y', '7'],
+            label_length: 2,
+            move_count: 2_000,
+            max_move_size: 30,
+        };
+        let (cargo_bay, move_commands) = generate_manifest(&config, 42).unwrap();
+        let manifest = to_manifest(&cargo_bay, &move_commands);
+        let (same_cargo_bay, same_move_commands) = generate_manifest(&config, 42).unwrap();
+        assert_eq!(to_manifest(&same_cargo_bay, &same_move_commands), manifest);
+        assert_ne!(to_manifest(&generate_manifest(&config, 43).unwrap().0, &[]), to_manifest(&cargo_bay, &[]));
+
+        assert_eq!(move_commands.len(), 2_000);
+        assert!(check_move_commands(&cargo_bay, &move_commands).is_empty());
+        let crate_count: usize = cargo_bay.heights().iter().sum();
+        let cranes: [&dyn Crane; 3] = [&CrateMover9000, &CrateMover9001, &CapacityCrane::new(4).unwrap()];
+        for crane in cranes {
+            let mut cargo_bay = cargo_bay.clone();
+            for move_command in move_commands.iter() {
+                cargo_bay.apply(move_command, crane).unwrap();
+            }
+            assert_eq!(cargo_bay.heights().iter().sum::<usize>(), crate_count);
+        }
+
+        let (parsed_cargo_bay, _) = parse_cargo_bay_and_move_commands(&manifest).unwrap();
+        assert_eq!(parsed_cargo_bay, cargo_bay);
+    }
+
+    #[test]
+    fn rejects_impossible_configs() {
+        let config = GeneratorConfig { max_height: 0, ..GeneratorConfig::default() };
+        assert!(generate_manifest(&config, 1).is_err());
+        assert!(generate_manifest(&GeneratorConfig { stack_count: 0, ..GeneratorConfig::default() }, 1).is_err());
+        assert!(generate_manifest(&GeneratorConfig { alphabet: Vec::new(), ..GeneratorConfig::default() }, 1).is_err());
+        assert!(generate_manifest(&GeneratorConfig { alphabet: vec!['-'], ..GeneratorConfig::default() }, 1).is_err());
+        let config = GeneratorConfig { move_count: 0, ..config };
+        assert_eq!(generate_manifest(&config, 1).unwrap().0.heights(), vec![0; 9]);
+    }
+}
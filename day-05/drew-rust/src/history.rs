@@ -0,0 +1,177 @@
+//! Undo and redo for a cargo bay. Every applied move is recorded with the
+//! lifts the crane made, which is enough to run it backwards: lowering the
+//! same lifts from the destination onto the origin in reverse order puts every
+//! crate back where it was, whatever crane made the move.
+
+use aoc_common_lib::error::{ AocError, Result };
+
+use crate::crane::Crane;
+use crate::model::{ CargoBay, MoveCommand };
+
+/// A move command together with the number of crates each lift carried.
+#[derive(Debug, Clone)]
+pub struct AppliedMove {
+    move_command: MoveCommand,
+    lifts: Vec<usize>,
+}
+
+impl AppliedMove {
+    pub fn move_command(&self) -> &MoveCommand {
+        &self.move_command
+    }
+
+    pub fn lifts(&self) -> &[usize] {
+        &self.lifts
+    }
+
+    fn redo(&self, cargo_bay: &mut CargoBay) {
+        for lift in self.lifts.iter() {
+            cargo_bay.transfer(self.move_command.origin(), self.move_command.destination(), *lift);
+        }
+    }
+
+    fn undo(&self, cargo_bay: &mut CargoBay) {
+        for lift in self.lifts.iter().rev() {
+            cargo_bay.transfer(self.move_command.destination(), self.move_command.origin(), *lift);
+        }
+    }
+}
+
+/// A cargo bay that remembers the moves applied to it. Step 0 is the bay
+/// before any move, step N the bay after the first N moves.
+#[derive(Debug, Clone)]
+pub struct History {
+    cargo_bay: CargoBay,
+    moves: Vec<AppliedMove>,
+    /// Number of `moves` currently applied to the bay, the rest can be redone.
+    step: usize,
+}
+
+impl History {
+    pub fn new(cargo_bay: CargoBay) -> Self {
+        Self {
+            cargo_bay,
+            moves: Vec::new(),
+            step: 0,
+        }
+    }
+
+    /// Apply every move command in turn, leaving the history at the last step.
+    pub fn record(cargo_bay: CargoBay, move_commands: &[MoveCommand], crane: &dyn Crane) -> Result<Self> {
+        let mut history = Self::new(cargo_bay);
+        for move_command in move_commands.iter() {
+            history.apply(move_command, crane)?;
+        }
+        Ok(history)
+    }
+
+    /// The bay at the current step.
+    pub fn cargo_bay(&self) -> &CargoBay {
+        &self.cargo_bay
+    }
+
+    /// The current step.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Number of recorded moves, including those that have been undone.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    pub fn moves(&self) -> &[AppliedMove] {
+        &self.moves
+    }
+
+    /// Apply a move at the current step. Moves that were undone are forgotten.
+    pub fn apply(&mut self, move_command: &MoveCommand, crane: &dyn Crane) -> Result<()> {
+        let lifts = self.cargo_bay.apply_lifts(move_command, crane)?;
+        self.moves.truncate(self.step);
+        self.moves.push(AppliedMove { move_command: move_command.clone(), lifts });
+        self.step += 1;
+        Ok(())
+    }
+
+    /// Take back the last applied move, returning it, or `None` at step 0.
+    pub fn undo(&mut self) -> Option<&MoveCommand> {
+        if self.step == 0 {
+            return None;
+        }
+        self.step -= 1;
+        let applied_move = &self.moves[self.step];
+        applied_move.undo(&mut self.cargo_bay);
+        Some(&applied_move.move_command)
+    }
+
+    /// Apply the last undone move again, returning it, or `None` when nothing
+    /// has been undone.
+    pub fn redo(&mut self) -> Option<&MoveCommand> {
+        let applied_move = match self.moves.get(self.step) {
+            Some(applied_move) => applied_move,
+            None => {
+                return None;
+            }
+        };
+        applied_move.redo(&mut self.cargo_bay);
+        self.step += 1;
+        Some(&applied_move.move_command)
+    }
+
+    /// Undo or redo moves until the bay is at `step`.
+    pub fn seek(&mut self, step: usize) -> Result<()> {
+        if step > self.moves.len() {
+            return Err(AocError::Usage(format!("There is no step {}, only {} moves were recorded", step, self.len())));
+        }
+        while self.step > step {
+            self.undo();
+        }
+        while self.step < step {
+            self.redo();
+        }
+        Ok(())
+    }
+
+    /// The bay at `step`, leaving the history where it is.
+    pub fn at(&self, step: usize) -> Result<CargoBay> {
+        let mut history = self.clone();
+        history.seek(step)?;
+        Ok(history.cargo_bay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::{ CapacityCrane, CrateMover9000 };
+    use crate::parser::parse_cargo_bay_and_move_commands;
+    use crate::top_ids;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn travels_through_moves() {
+        let (cargo_bay, move_commands) = parse_cargo_bay_and_move_commands(EXAMPLE).unwrap();
+        let crane = CapacityCrane::new(2).unwrap();
+        let mut history = History::record(cargo_bay.clone(), &move_commands, &crane).unwrap();
+        assert_eq!(history.step(), 4);
+        assert_eq!(top_ids(history.cargo_bay()), "MCZ");
+
+        history.seek(0).unwrap();
+        assert_eq!(history.cargo_bay(), &cargo_bay);
+        assert!(history.undo().is_none());
+        assert_eq!(format!("{}", history.redo().unwrap()), "move 1 from 2 to 1");
+        assert_eq!(top_ids(&history.at(4).unwrap()), "MCZ");
+        assert!(history.at(5).is_err());
+
+        // Applying a move after an undo drops the moves that could be redone
+        history.apply(&move_commands[3], &CrateMover9000).unwrap();
+        assert_eq!((history.step(), history.len()), (2, 2));
+        assert!(history.redo().is_none());
+        assert_eq!(top_ids(&history.at(0).unwrap()), top_ids(&cargo_bay));
+    }
+}
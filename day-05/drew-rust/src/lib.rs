@@ -0,0 +1,104 @@
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+
+use aoc_common_lib::error::Result;
+use aoc_common_lib::solution::Solution;
+
+use crate::crane::{ Crane, CrateMover9000, CrateMover9001 };
+use crate::model::{ CargoBay, MoveCommands };
+use crate::parser::parse_cargo_bay_and_move_commands;
+
+pub mod crane;
+pub mod generate;
+pub mod history;
+pub mod model;
+pub mod parser;
+pub mod planner;
+pub mod provenance;
+pub mod replay;
+pub mod stream;
+pub mod svg;
+pub mod validate;
+pub mod writer;
+
+/// Apply every move command to a copy of the cargo bay with `crane` and read
+/// off the id of the top crate of each stack.
+pub fn top_crates_after(cargo_bay: &CargoBay, move_commands: &MoveCommands, crane: &dyn Crane) -> Result<String> {
+    let mut cargo_bay = cargo_bay.clone();
+    for move_command in move_commands.iter() {
+        cargo_bay.apply(move_command, crane)?;
+    }
+    Ok(
+        cargo_bay
+            .top()
+            .iter()
+            .map(|cargo_crate| cargo_crate.id())
+            .collect()
+    )
+}
+
+/// Ids of the top crate of each stack, for comparing bays in tests.
+#[cfg(test)]
+pub(crate) fn top_ids(cargo_bay: &CargoBay) -> String {
+    cargo_bay
+        .top()
+        .iter()
+        .map(|cargo_crate| cargo_crate.id())
+        .collect()
+}
+
+#[derive(Debug, Default)]
+pub struct SupplyStacks;
+
+impl Solution for SupplyStacks {
+    type Input = (CargoBay, MoveCommands);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_cargo_bay_and_move_commands(input)
+    }
+
+    /// The CrateMover 9000 moves crates one at a time.
+    fn part1(&self, (cargo_bay, move_commands): &Self::Input) -> Result<String> {
+        top_crates_after(cargo_bay, move_commands, &CrateMover9000)
+    }
+
+    /// The CrateMover 9001 moves a whole stack of crates at once.
+    fn part2(&self, (cargo_bay, move_commands): &Self::Input) -> Result<String> {
+        top_crates_after(cargo_bay, move_commands, &CrateMover9001)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::CapacityCrane;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn solves_example() {
+        let manifest = SupplyStacks.parse(EXAMPLE).unwrap();
+        assert_eq!(SupplyStacks.part1(&manifest).unwrap(), "CMZ");
+        assert_eq!(SupplyStacks.part2(&manifest).unwrap(), "MCD");
+        let (cargo_bay, move_commands) = &manifest;
+        assert_eq!(top_crates_after(cargo_bay, move_commands, &CapacityCrane::new(2).unwrap()).unwrap(), "MCZ");
+    }
+
+    #[test]
+    fn reports_moves_between_missing_stacks_on_their_line() {
+        let input = "[A]    \n[B] [C]\n 1   2 \n\nmove 1 from 1 to 2\nmove 1 from 3 to 1\n";
+        let manifest = SupplyStacks.parse(input).unwrap();
+        let err = SupplyStacks.part1(&manifest).unwrap_err();
+        let expected = [
+            "Invalid command `move 1 from 3 to 1`: origin stack does not exist",
+            " --> <input>:6:1",
+            "  |",
+            "6 | move 1 from 3 to 1",
+            "  | ^^^^^^^^^^^^^^^^^^",
+        ];
+        assert_eq!(format!("{}", err), expected.join("\n"));
+    }
+}
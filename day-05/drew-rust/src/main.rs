@@ -1,43 +1,108 @@
-extern crate pest;
-#[macro_use]
-extern crate pest_derive;
+use advent_day_5::crane::{crane_by_name, Crane, CRANE_NAMES};
+use advent_day_5::history::History;
+use advent_day_5::model::{CargoBay, MoveCommands};
+use advent_day_5::parser::parse_cargo_bay_and_move_commands;
+use advent_day_5::planner::{plan, Target, DEFAULT_MAX_STATES};
+use advent_day_5::provenance::Provenance;
+use advent_day_5::replay::{render_step, replay, Highlight};
+use advent_day_5::stream::{apply_move_commands, read_cargo_bay};
+use advent_day_5::svg::{render_report, render_svg};
+use advent_day_5::validate::{check_manifest, ManifestWarning, Strictness};
+use advent_day_5::writer::to_manifest;
+use aoc_common_lib::error::{AocError, OnLine, Result};
+use aoc_common_lib::input::{Input, InputReader};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+use std::{fs, thread, time::Duration};
 
-use crate::parser::parse_cargo_bay_and_move_commands;
-use aoc_common_lib::error::RuntimeError;
-use std::{env, error::Error};
-
-mod model;
-mod parser;
-
-// Override the alias to use `Box<error::Error>`.
-type Result<T> = std::result::Result<T, Box<dyn Error>>;
-
-fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        return Err(Box::new(RuntimeError::new(String::from(
-            "Must provide input file path",
-        ))));
-    }
-    let input_path = &args[1];
-    let move_stacks_together = args
-        .get(2)
-        .unwrap_or(&String::from("false"))
-        .parse::<bool>()?;
-
-    let results = parse_cargo_bay_and_move_commands(input_path)?;
-    let mut cargo_bay = results.0;
-    let move_commands = results.1;
-
-    println!("Before:\n{}", &cargo_bay);
+#[derive(Parser)]
+#[command(about = "Rearrange the supply stacks with a crane")]
+struct Cli {
+    /// Cargo manifest, `-` reads stdin
+    input: String,
+    /// Crane to move crates with, `false` and `true` are kept for the 9000 and 9001
+    #[arg(default_value = "9000", long_help = format!("Crane to move crates with: {}", CRANE_NAMES))]
+    crane: String,
+    /// Print the bay after every move with the moved crates highlighted
+    #[arg(long)]
+    replay: bool,
+    /// Animate the replay in place, pausing this many milliseconds per move
+    #[arg(long, requires = "replay")]
+    delay: Option<u64>,
+    /// Write the bay after every move to numbered files in this directory
+    #[arg(long)]
+    frames: Option<PathBuf>,
+    /// Also print the bay as it was after this many moves
+    #[arg(long, conflicts_with_all = ["replay", "frames"])]
+    at: Option<usize>,
+    /// Fail on moves from stacks that run short and on crates drawn beyond the
+    /// last stack, instead of warning about them after the run
+    #[arg(long)]
+    strict: bool,
+    /// Apply moves as they are read instead of loading the whole manifest, for
+    /// move lists too large to keep in memory
+    #[arg(long, conflicts_with_all = ["replay", "frames", "at", "trail", "touching"])]
+    stream: bool,
+    /// Report where every crate with this label started, which moves carried
+    /// it and where it ended up
+    #[arg(long)]
+    trail: Option<String>,
+    /// List the moves that took crates from or put crates on this stack
+    #[arg(long)]
+    touching: Option<usize>,
+    /// Instead of running the moves, print a manifest with the fewest moves that
+    /// leave the stacks drawn in this manifest
+    #[arg(long, conflicts_with_all = ["stream", "plan_top"])]
+    plan_to: Option<String>,
+    /// Instead of running the moves, print a manifest with the fewest moves that
+    /// leave these labels on top of the stacks, e.g. `CMZ`, or `AB,C` for
+    /// labels of more than one character
+    #[arg(long, conflicts_with = "stream")]
+    plan_top: Option<String>,
+    /// Write an SVG drawing of the bay after the last move to this file
+    #[arg(long, conflicts_with = "stream")]
+    svg: Option<PathBuf>,
+    /// Write an HTML page stepping through every move with a slider to this file
+    #[arg(long, conflicts_with = "stream")]
+    html: Option<PathBuf>,
+    /// Bay states to search before giving up on a plan
+    #[arg(long, default_value_t = DEFAULT_MAX_STATES)]
+    max_states: usize,
+}
 
-    for move_command in move_commands.iter() {
-        cargo_bay.apply(move_command, move_stacks_together)?;
+fn run_replay(cli: &Cli, cargo_bay: &mut CargoBay, move_commands: &MoveCommands, crane: &dyn Crane) -> Result<()> {
+    if let Some(directory) = &cli.frames {
+        fs::create_dir_all(directory)?;
     }
+    replay(cargo_bay, move_commands, crane, |step, cargo_bay| {
+        if let Some(directory) = &cli.frames {
+            let frame = render_step(step, cargo_bay, Highlight::Parentheses);
+            fs::write(directory.join(format!("frame-{:06}.txt", step.number)), frame)?;
+        }
+        if cli.replay {
+            if let Some(delay) = cli.delay {
+                // Clear the terminal so that each frame replaces the last
+                print!("\x1b[2J\x1b[H");
+                print!("{}", render_step(step, cargo_bay, Highlight::Ansi));
+                thread::sleep(Duration::from_millis(delay));
+            } else {
+                print!("{}", render_step(step, cargo_bay, Highlight::Ansi));
+            }
+        }
+        Ok(())
+    })
+}
 
-    println!("Applied {} move commands...\n", move_commands.len());
+fn print_result(
+    cargo_bay: &CargoBay,
+    move_count: usize,
+    crane: &dyn Crane,
+    warnings: Vec<ManifestWarning>,
+    input_name: &Path,
+) {
+    println!("Applied {} move commands with crane {}...\n", move_count, crane.name());
 
-    println!("After:\n{}", &cargo_bay);
+    println!("After:\n{}", cargo_bay);
 
     println!(
         "Top Crates: {}",
@@ -53,10 +118,150 @@ fn main() -> Result<()> {
         cargo_bay
             .top()
             .iter()
-            .map(|cargo_crate| format!("{}", cargo_crate.id()))
-            .collect::<Vec<String>>()
+            .map(|cargo_crate| cargo_crate.id())
+            .collect::<Vec<&str>>()
             .join("")
     );
 
+    for warning in warnings {
+        print_warning(warning, input_name);
+    }
+}
+
+fn print_warning(mut warning: ManifestWarning, input_name: &Path) {
+    warning.location = warning.location.map(|location| location.in_file(input_name));
+    eprintln!("{}", warning);
+}
+
+fn print_provenance(cli: &Cli, provenance: &Provenance) -> Result<()> {
+    if let Some(label) = &cli.trail {
+        let serials = provenance.serials_labelled(label);
+        if serials.is_empty() {
+            return Err(AocError::Usage(format!("No crate is labelled `{}`", label)));
+        }
+        println!();
+        for serial in serials {
+            print!("{}", provenance.trail_report(serial));
+        }
+    }
+
+    if let Some(stack) = cli.touching {
+        if stack == 0 || stack > provenance.cargo_bay().stack_count() {
+            return Err(AocError::Usage(format!("There is no stack {}", stack)));
+        }
+        println!("\nMoves touching stack {}:", stack);
+        for (step, move_command) in provenance.moves_touching(stack - 1) {
+            println!("  move {}: {}", step, move_command);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_plan(cli: &Cli, cargo_bay: &CargoBay, crane: &dyn Crane) -> Result<()> {
+    let target = match (&cli.plan_to, &cli.plan_top) {
+        (Some(path), _) => {
+            let input = Input::open(path)?;
+            let (target_cargo_bay, _) = parse_cargo_bay_and_move_commands(input.as_str()).in_file(input.name())?;
+            Target::arrangement_of(&target_cargo_bay)
+        }
+        (None, Some(top)) => Target::top(top),
+        (None, None) => {
+            return Ok(());
+        }
+    };
+
+    let move_commands = plan(cargo_bay, &target, crane, cli.max_states)?;
+    print!("{}", to_manifest(cargo_bay, &move_commands));
+
+    Ok(())
+}
+
+fn run_stream(cli: &Cli, crane: &dyn Crane, strictness: Strictness) -> Result<()> {
+    let input = InputReader::open(&cli.input)?;
+    let input_name = input.name().to_path_buf();
+
+    let (mut cargo_bay, mut warnings, move_commands) = read_cargo_bay(input).in_file(&input_name)?;
+    if strictness == Strictness::Strict && !warnings.is_empty() {
+        return Err(AocError::from(warnings.remove(0)).in_file(&input_name));
+    }
+
+    println!("Before:\n{}", &cargo_bay);
+
+    // Moves that run short are reported as they are found, there may be too
+    // many to keep until the end
+    let move_count = apply_move_commands(&mut cargo_bay, move_commands, crane, strictness, |warning| {
+        print_warning(warning, &input_name)
+    }).in_file(&input_name)?;
+
+    print_result(&cargo_bay, move_count, crane, warnings, &input_name);
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let crane = match cli.crane.as_str() {
+        "false" => crane_by_name("9000")?,
+        "true" => crane_by_name("9001")?,
+        name => crane_by_name(name)?,
+    };
+
+    let strictness = if cli.strict {
+        Strictness::Strict
+    } else {
+        Strictness::Lenient
+    };
+
+    if cli.stream {
+        return run_stream(&cli, crane.as_ref(), strictness);
+    }
+
+    let input = Input::open(&cli.input)?;
+
+    let (mut cargo_bay, move_commands, warnings) =
+        check_manifest(input.as_str(), strictness).in_file(input.name())?;
+
+    if cli.plan_to.is_some() || cli.plan_top.is_some() {
+        return run_plan(&cli, &cargo_bay, crane.as_ref());
+    }
+
+    println!("Before:\n{}", &cargo_bay);
+
+    if let Some(path) = &cli.html {
+        let title = format!("{}", input.name().display());
+        let report = render_report(&title, &cargo_bay, &move_commands, crane.as_ref()).in_file(input.name())?;
+        fs::write(path, report)?;
+    }
+
+    let provenance = if cli.trail.is_some() || cli.touching.is_some() {
+        Some(Provenance::record(cargo_bay.clone(), &move_commands, crane.as_ref()).in_file(input.name())?)
+    } else {
+        None
+    };
+
+    if cli.replay || cli.frames.is_some() {
+        run_replay(&cli, &mut cargo_bay, &move_commands, crane.as_ref()).in_file(input.name())?;
+    } else if let Some(step) = cli.at {
+        // Only keep every move around when asked to look back at one
+        let history = History::record(cargo_bay, &move_commands, crane.as_ref()).in_file(input.name())?;
+        println!("After move {}:\n{}", step, history.at(step)?);
+        cargo_bay = history.cargo_bay().clone();
+    } else {
+        for move_command in move_commands.iter() {
+            cargo_bay.apply(move_command, crane.as_ref()).in_file(input.name())?;
+        }
+    }
+
+    print_result(&cargo_bay, move_commands.len(), crane.as_ref(), warnings, input.name());
+
+    if let Some(path) = &cli.svg {
+        fs::write(path, render_svg(&cargo_bay))?;
+    }
+
+    if let Some(provenance) = provenance {
+        print_provenance(&cli, &provenance)?;
+    }
+
     Ok(())
 }
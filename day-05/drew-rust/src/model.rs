@@ -1,27 +1,46 @@
-use std::{ error::Error, fmt::Display };
+use std::fmt::Display;
 
-use aoc_common_lib::error::RuntimeError;
+use aoc_common_lib::diagnostic::Location;
+use aoc_common_lib::error::{ AocError, Result };
 
-// Override the alias to use `Box<error::Error>`.
-type Result<T> = std::result::Result<T, Box<dyn Error>>;
+use crate::crane::Crane;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CargoCrate {
-    id: char,
+    id: String,
+    serial: usize,
 }
 impl CargoCrate {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: String::from(id),
+            serial: 0,
+        }
+    }
+
     pub fn parse(as_str: &str) -> Option<CargoCrate> {
-        if as_str.trim().is_empty() {
+        let as_str = as_str.trim();
+        if as_str.is_empty() {
             None
         } else {
-            Some(CargoCrate {
-                id: as_str.trim()[1..2].chars().collect::<Vec<char>>()[0],
-            })
+            Some(CargoCrate::new(as_str.trim_start_matches('[').trim_end_matches(']')))
         }
     }
 
-    pub fn id(&self) -> char {
-        self.id
+    /// The label between the brackets, a single letter in the puzzle.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Identifies the crate however it is moved, labels can repeat. Crates are
+    /// numbered from 0 by [`CargoBay::new`], stack by stack from the floor up.
+    pub fn serial(&self) -> usize {
+        self.serial
+    }
+
+    /// Number of characters the crate takes up when drawn, brackets included.
+    pub fn width(&self) -> usize {
+        self.id.chars().count() + 2
     }
 }
 
@@ -31,7 +50,7 @@ impl Display for CargoCrate {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CargoStack {
     id: usize,
     stack: Vec<CargoCrate>,
@@ -39,42 +58,21 @@ pub struct CargoStack {
 
 impl CargoStack {
     fn add(&mut self, cargo_crate: &Option<CargoCrate>) {
-        match cargo_crate {
-            Some(cargo_crate) => self.stack.push(CargoCrate { id: cargo_crate.id }),
-            None => (),
+        if let Some(cargo_crate) = cargo_crate {
+            self.stack.push(cargo_crate.clone());
         }
     }
 
-    fn add_stack(&mut self, cargo_stack: CargoStack) {
-        for cargo_crate in cargo_stack.stack {
-            self.add(&Some(cargo_crate));
-        }
-    }
-
-    fn remove(&mut self) -> Option<CargoCrate> {
-        self.stack.pop()
+    /// Lift the top `size` crates off the stack, keeping their order. Only the
+    /// crates there are get lifted when the stack is too short.
+    fn lift(&mut self, size: usize) -> Vec<CargoCrate> {
+        let split_at = self.stack.len().saturating_sub(size);
+        self.stack.split_off(split_at)
     }
 
-    fn remove_stack(&mut self, size: usize, collect_in_place: bool) -> Self {
-        let mut cargo_stack: Vec<CargoCrate> = Vec::new();
-        for _ in 0..size {
-            match self.remove() {
-                Some(cargo_crate) => {
-                    if collect_in_place {
-                        cargo_stack.insert(0, cargo_crate);
-                    } else {
-                        cargo_stack.push(cargo_crate);
-                    }
-                }
-                None => {
-                    continue;
-                }
-            }
-        }
-        CargoStack {
-            id: 0,
-            stack: cargo_stack,
-        }
+    /// Lower crates onto the top of the stack, keeping their order.
+    fn lower(&mut self, cargo_crates: Vec<CargoCrate>) {
+        self.stack.extend(cargo_crates);
     }
 
     fn get(&self, height: usize) -> Option<&CargoCrate> {
@@ -82,7 +80,7 @@ impl CargoStack {
     }
 
     fn top(&self) -> Option<CargoCrate> {
-        self.stack.last().map(|last| CargoCrate { id: last.id })
+        self.stack.last().cloned()
     }
 
     fn height(&self) -> usize {
@@ -105,7 +103,7 @@ impl Display for CargoStack {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CargoBay {
     stacks: Vec<CargoStack>,
 }
@@ -134,34 +132,52 @@ impl CargoBay {
             }
         }
 
+        let cargo_crates = cargo_bay_stacks.iter_mut().flat_map(|cargo_stack| cargo_stack.stack.iter_mut());
+        for (serial, cargo_crate) in cargo_crates.enumerate() {
+            cargo_crate.serial = serial;
+        }
+
         CargoBay {
             stacks: cargo_bay_stacks,
         }
     }
 
-    pub fn apply(&mut self, move_command: &MoveCommand, move_stacks_together: bool) -> Result<bool> {
-        let origin_cargo_stack = self.stacks.get_mut(move_command.origin);
-        let moved_cargo_stack: CargoStack;
+    /// Move crates with `crane`, one lift at a time.
+    pub fn apply(&mut self, move_command: &MoveCommand, crane: &dyn Crane) -> Result<bool> {
+        self.apply_lifts(move_command, crane)?;
+        Ok(true)
+    }
 
-        match origin_cargo_stack {
-            Some(origin_cargo_stack) => {
-                moved_cargo_stack = origin_cargo_stack.remove_stack(move_command.size, move_stacks_together);
-            }
-            None => {
-                return Err(Box::new(RuntimeError::new(format!("Error applying move command: {}", move_command))));
-            }
+    /// Move crates with `crane` like [`CargoBay::apply`] and return how many
+    /// crates each lift carried. A move of more crates than the origin holds
+    /// only moves the crates there are.
+    pub fn apply_lifts(&mut self, move_command: &MoveCommand, crane: &dyn Crane) -> Result<Vec<usize>> {
+        if move_command.origin >= self.stacks.len() {
+            return Err(move_command.invalid("origin stack does not exist"));
         }
-
-        match self.stacks.get_mut(move_command.destination) {
-            Some(destination_cargo_stack) => {
-                destination_cargo_stack.add_stack(moved_cargo_stack);
-            }
-            None => {
-                return Err(Box::new(RuntimeError::new(format!("Error applying move command: {}", move_command))));
-            }
+        if move_command.destination >= self.stacks.len() {
+            return Err(move_command.invalid("destination stack does not exist"));
         }
 
-        Ok(true)
+        // The crane only plans lifts for crates that are there, however many
+        // the move asks for
+        let size = move_command.size.min(self.stacks[move_command.origin].stack.len());
+        Ok(
+            crane
+                .lifts(size)
+                .into_iter()
+                .map(|lift| self.transfer(move_command.origin, move_command.destination, lift))
+                .collect()
+        )
+    }
+
+    /// Lift up to `size` crates off `origin` in one go and lower them onto
+    /// `destination`, returning how many were moved. Both stacks must exist.
+    pub fn transfer(&mut self, origin: usize, destination: usize, size: usize) -> usize {
+        let cargo_crates = self.stacks[origin].lift(size);
+        let moved = cargo_crates.len();
+        self.stacks[destination].lower(cargo_crates);
+        moved
     }
 
     pub fn top(&self) -> Vec<CargoCrate> {
@@ -170,30 +186,72 @@ impl CargoBay {
             .filter_map(|cargo_stack| cargo_stack.top())
             .collect()
     }
-}
 
-impl Display for CargoBay {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    pub fn stack_count(&self) -> usize {
+        self.stacks.len()
+    }
+
+    /// The crate at `layer` of `stack`, both indexed from 0 with layer 0 being
+    /// the floor.
+    pub fn get(&self, stack: usize, layer: usize) -> Option<&CargoCrate> {
+        self.stacks.get(stack).and_then(|cargo_stack| cargo_stack.get(layer))
+    }
+
+    /// Number of characters each stack takes up in a drawing of the bay: as
+    /// wide as the widest crate and wide enough for the stack number below.
+    /// Bays of single letter crates and up to 99 stacks are 3 wide.
+    pub fn cell_width(&self) -> usize {
+        let widest_crate = self.stacks
+            .iter()
+            .flat_map(|cargo_stack| cargo_stack.stack.iter())
+            .map(|cargo_crate| cargo_crate.width())
+            .max()
+            .unwrap_or(3);
+        widest_crate.max(3).max(self.stacks.len().to_string().len() + 1)
+    }
+
+    /// Number of crates on each stack.
+    pub fn heights(&self) -> Vec<usize> {
+        self.stacks
+            .iter()
+            .map(|cargo_stack| cargo_stack.height())
+            .collect()
+    }
+
+    /// Write the bay like [`Display`] does, but with every crate rendered by
+    /// `render_crate(stack, layer, cargo_crate)`. Stacks and layers are
+    /// indexed from 0, layer 0 being the floor.
+    pub fn render_with(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        render_crate: &dyn Fn(usize, usize, &CargoCrate) -> String
+    ) -> std::fmt::Result {
         let max_height = self.stacks
             .iter()
             .map(|stack| stack.height())
             .max()
             .unwrap_or(0);
 
+        let cell_width = self.cell_width();
+
         for layer in (0..max_height).rev() {
             let mut layer_crates: Vec<String> = Vec::new();
 
-            for stack in self.stacks.iter() {
+            for (index, stack) in self.stacks.iter().enumerate() {
                 layer_crates.push(match stack.get(layer) {
-                    Some(cargo_crate) => format!("{}", cargo_crate),
-                    None => String::from("   "),
+                    // Pad by the drawn width, the rendered crate may hold escape codes
+                    Some(cargo_crate) => {
+                        let padding = " ".repeat(cell_width - cargo_crate.width());
+                        format!("{}{}", render_crate(index, layer, cargo_crate), padding)
+                    }
+                    None => " ".repeat(cell_width),
                 });
             }
             f.write_fmt(format_args!("{}\n", layer_crates.join(" ")))?;
         }
 
         for stack in self.stacks.iter() {
-            f.write_fmt(format_args!(" {}  ", stack.id))?;
+            f.write_fmt(format_args!(" {:<width$}", stack.id, width = cell_width))?;
         }
         f.write_str("\n")?;
 
@@ -201,11 +259,18 @@ impl Display for CargoBay {
     }
 }
 
-#[derive(Debug)]
+impl Display for CargoBay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render_with(f, &(|_, _, cargo_crate| format!("{}", cargo_crate)))
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct MoveCommand {
     size: usize,
     origin: usize,
     destination: usize,
+    location: Option<Location>,
 }
 
 impl MoveCommand {
@@ -214,8 +279,48 @@ impl MoveCommand {
             size,
             origin,
             destination,
+            location: None,
+        }
+    }
+
+    /// Remember the line of the manifest the command was read from, as it
+    /// was written.
+    pub fn on_line(mut self, line_number: usize, line: &str) -> Self {
+        self.location = Some(Location::line(line_number, line));
+        self
+    }
+
+    pub fn line_number(&self) -> Option<usize> {
+        self.location.as_ref().map(|location| location.line)
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// The command can't be carried out, located on its line when it was read
+    /// from a manifest.
+    fn invalid(&self, reason: &str) -> AocError {
+        let err = AocError::invalid_command(self, reason);
+        match &self.location {
+            Some(location) => err.on_line(location.line, &location.text),
+            None => err,
         }
     }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Index of the stack crates are moved from, counted from 0.
+    pub fn origin(&self) -> usize {
+        self.origin
+    }
+
+    /// Index of the stack crates are moved to, counted from 0.
+    pub fn destination(&self) -> usize {
+        self.destination
+    }
 }
 
 impl Display for MoveCommand {
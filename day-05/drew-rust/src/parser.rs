@@ -1,84 +1,112 @@
-use pest::Parser;
+use pest::{iterators::Pair, Parser};
 
-use std::error::Error;
+use aoc_common_lib::error::{OnLine, ParseError, Result};
+use aoc_common_lib::utility::blocks;
 
-use aoc_common_lib::{error::RuntimeError, utility::read_lines};
+use aoc_common_lib::diagnostic::Location;
 
 use crate::model::{CargoBay, CargoCrate, MoveCommand, MoveCommands};
-
-// Override the alias to use `Box<error::Error>`.
-type Result<T> = std::result::Result<T, Box<dyn Error>>;
+use crate::validate::{ManifestIssue, ManifestWarning};
 
 #[derive(Parser)]
 #[grammar = "grammar.pest"] // relative to src
 pub struct CargoManifestParser;
 
+/// Start and end byte offset of a crate or stack number within its line.
+type Span = (usize, usize);
+
+/// The crates of one row of the drawing and where they were drawn.
+type DrawnRow = Vec<(Span, CargoCrate)>;
+
 enum ManifestLine {
-    CrateRow(Vec<Option<CargoCrate>>),
-    StackCount(usize),
+    CrateRow(DrawnRow),
+    StackRow(Vec<Span>),
     MoveCommand(MoveCommand),
 }
 
+fn span_of(pair: &Pair<Rule>) -> Span {
+    (pair.as_span().start(), pair.as_span().end())
+}
+
 fn parse_crate_row(crate_row_pairs: pest::iterators::Pairs<Rule>) -> Result<ManifestLine> {
     let mut crate_row = Vec::new();
 
     for pair in crate_row_pairs {
         match pair.as_rule() {
-            Rule::cargo_crate => crate_row.push(CargoCrate::parse(pair.as_str())),
-            Rule::cargo_crate_row
+            Rule::cargo_crate => {
+                if let Some(cargo_crate) = CargoCrate::parse(pair.as_str()) {
+                    crate_row.push((span_of(&pair), cargo_crate));
+                }
+            }
+            Rule::cargo_crate_label
+            | Rule::cargo_crate_row
             | Rule::cargo_crate_stack
             | Rule::cargo_crate_stack_row
             | Rule::move_command
-            | Rule::manifest_line => unreachable!(),
+            | Rule::manifest_line
+            | Rule::EOI => unreachable!(),
         }
     }
 
     Ok(ManifestLine::CrateRow(crate_row))
 }
 
+/// Stacks are numbered from 1 left to right, crates are placed on the stack
+/// whose number they are drawn above.
 fn parse_stack_row(stack_row_pairs: pest::iterators::Pairs<Rule>) -> Result<ManifestLine> {
-    Ok(ManifestLine::StackCount(stack_row_pairs.count()))
+    let mut stack_columns = Vec::new();
+
+    for (index, pair) in stack_row_pairs.enumerate() {
+        if parse_number(&pair)? != index + 1 {
+            let message = format!("expected stack number {}", index + 1);
+            return Err(ParseError::new(&message)).at_token(1, pair.get_input(), pair.as_str());
+        }
+        stack_columns.push(span_of(&pair));
+    }
+
+    Ok(ManifestLine::StackRow(stack_columns))
+}
+
+/// Parse a number matched by the grammar. Each manifest line is parsed on its
+/// own, so errors are located on line 1 and moved to the real line by the caller.
+fn parse_number(pair: &Pair<Rule>) -> Result<usize> {
+    pair.as_str()
+        .parse::<usize>()
+        .at_token(1, pair.get_input(), pair.as_str())
+}
+
+/// Stacks are numbered from 1 in the manifest but indexed from 0 in the bay.
+fn parse_stack_number(pair: &Pair<Rule>) -> Result<usize> {
+    match parse_number(pair)?.checked_sub(1) {
+        Some(index) => Ok(index),
+        None => Err(ParseError::new("stack numbers start at 1"))
+            .at_token(1, pair.get_input(), pair.as_str()),
+    }
 }
 
 fn parse_move_command(
     mut move_command_pairs: pest::iterators::Pairs<Rule>,
 ) -> Result<ManifestLine> {
-    let size;
-    let origin;
-    let destination;
-
-    match move_command_pairs.next() {
-        Some(pair) => {
-            size = pair.as_str().parse::<usize>()?;
-        }
+    let size = match move_command_pairs.next() {
+        Some(pair) => parse_number(&pair)?,
         None => {
-            return Err(Box::new(RuntimeError::new(String::from(
-                "unexpected missing pair",
-            ))));
+            return Err(ParseError::new("unexpected missing pair").into());
         }
-    }
+    };
 
-    match move_command_pairs.next() {
-        Some(pair) => {
-            origin = pair.as_str().parse::<usize>()? - 1;
-        }
+    let origin = match move_command_pairs.next() {
+        Some(pair) => parse_stack_number(&pair)?,
         None => {
-            return Err(Box::new(RuntimeError::new(String::from(
-                "unexpected missing pair",
-            ))));
+            return Err(ParseError::new("unexpected missing pair").into());
         }
-    }
+    };
 
-    match move_command_pairs.next() {
-        Some(pair) => {
-            destination = pair.as_str().parse::<usize>()? - 1;
-        }
+    let destination = match move_command_pairs.next() {
+        Some(pair) => parse_stack_number(&pair)?,
         None => {
-            return Err(Box::new(RuntimeError::new(String::from(
-                "unexpected missing pair",
-            ))));
+            return Err(ParseError::new("unexpected missing pair").into());
         }
-    }
+    };
 
     Ok(ManifestLine::MoveCommand(MoveCommand::new(
         size,
@@ -93,66 +121,179 @@ fn parse_manifest_line(manifest_line: pest::iterators::Pair<Rule>) -> Result<Man
             Rule::cargo_crate_row => parse_crate_row(pair.into_inner()),
             Rule::cargo_crate_stack_row => parse_stack_row(pair.into_inner()),
             Rule::move_command => parse_move_command(pair.into_inner()),
-            Rule::manifest_line | Rule::cargo_crate | Rule::cargo_crate_stack => unreachable!(),
+            Rule::manifest_line
+            | Rule::cargo_crate
+            | Rule::cargo_crate_label
+            | Rule::cargo_crate_stack
+            | Rule::EOI => unreachable!(),
         },
-        None => Err(Box::new(RuntimeError::new(String::from(
-            "unexpected missing pair",
-        )))),
+        None => Err(ParseError::new("unexpected missing pair").into()),
     }
 }
 
-pub fn parse_cargo_bay_and_move_commands(
-    input_file_path: &str,
-) -> Result<(CargoBay, MoveCommands)> {
-    let mut cargo_crate_rows: Vec<Vec<Option<CargoCrate>>> = Vec::new();
-    let mut cargo_crate_row_count: usize = 0;
+pub fn parse_cargo_bay_and_move_commands(input: &str) -> Result<(CargoBay, MoveCommands)> {
+    let (cargo_bay, move_commands, _) = parse_manifest(input)?;
+
+    Ok((cargo_bay, move_commands))
+}
+
+/// Parse a single line of the move list, for reading moves one at a time.
+pub fn parse_move_command_line(line_number: usize, line: &str) -> Result<MoveCommand> {
+    let manifest_line = match CargoManifestParser::parse(Rule::manifest_line, line)
+        .on_line(line_number, line)?
+        .next()
+    {
+        Some(pair) => pair,
+        None => {
+            return Err(ParseError::new("unexpected missing pair").into());
+        }
+    };
+
+    match parse_manifest_line(manifest_line).on_line(line_number, line)? {
+        ManifestLine::MoveCommand(move_command) => Ok(move_command.on_line(line_number, line)),
+        ManifestLine::CrateRow(_) | ManifestLine::StackRow(_) => {
+            Err(ParseError::new("expected a move command")).on_line(line_number, line)
+        }
+    }
+}
+
+/// A drawn row of crates, placed on the stacks whose number they are drawn
+/// above. Crates to the right of the last stack number are left out with a
+/// warning.
+fn place_crate_row(
+    line_number: usize,
+    line: &str,
+    crate_row: DrawnRow,
+    stack_columns: &[Span],
+) -> Result<(Vec<Option<CargoCrate>>, Option<ManifestWarning>)> {
+    let mut placed_row: Vec<Option<CargoCrate>> = vec![None; stack_columns.len()];
+    let mut left_out: Vec<Span> = Vec::new();
+    let last_column_end = stack_columns.last().map(|(_, end)| *end).unwrap_or(0);
+
+    for ((start, end), cargo_crate) in crate_row {
+        let token = &line[start..end];
+        let stack = stack_columns
+            .iter()
+            .position(|(column_start, column_end)| start < *column_end && *column_start < end);
+        match stack {
+            Some(stack) if placed_row[stack].is_none() => {
+                placed_row[stack] = Some(cargo_crate);
+            }
+            Some(stack) => {
+                let message = format!("more than one crate is drawn above stack {}", stack + 1);
+                return Err(ParseError::new(&message)).at_token(line_number, line, token);
+            }
+            None if start >= last_column_end => left_out.push((start, end)),
+            None => {
+                return Err(ParseError::new("crate is not drawn above a stack number"))
+                    .at_token(line_number, line, token);
+            }
+        }
+    }
+
+    let warning = left_out.first().map(|(start, _)| ManifestWarning {
+        issue: ManifestIssue::CratesBeyondStacks {
+            count: left_out.len(),
+            stack_count: stack_columns.len(),
+        },
+        location: Some(Location::token(line_number, line, line[*start..].trim_end())),
+    });
+
+    Ok((placed_row, warning))
+}
+
+/// Parse a manifest like [`parse_cargo_bay_and_move_commands`], also returning
+/// the crates that were left out of the bay. Move commands remember the line
+/// they were read from.
+pub fn parse_manifest(input: &str) -> Result<(CargoBay, MoveCommands, Vec<ManifestWarning>)> {
+    let mut cargo_crate_rows: Vec<(usize, &str, DrawnRow)> = Vec::new();
+    let mut stack_columns: Vec<Span> = Vec::new();
     let mut move_commands: Vec<MoveCommand> = Vec::new();
 
-    if let Ok(lines) = read_lines(input_file_path) {
-        for line in lines {
-            match line {
-                Ok(line) => {
-                    if line.is_empty() {
-                        continue;
-                    }
+    // The drawing of the stacks and the move list are separate blocks, but each
+    // line is recognised by the grammar so they can be handled alike.
+    for (line_number, line) in blocks(input).flatten() {
+        let cargo_manifest_contents =
+            CargoManifestParser::parse(Rule::manifest_line, line).on_line(line_number, line)?;
 
-                    let cargo_manifest_contents =
-                        match CargoManifestParser::parse(Rule::manifest_line, &line) {
-                            Ok(manifest_contents) => manifest_contents,
-                            Err(err) => {
-                                return Err(Box::new(err));
-                            }
-                        };
-
-                    for manifest_line in cargo_manifest_contents {
-                        match manifest_line.as_rule() {
-                            Rule::manifest_line => match parse_manifest_line(manifest_line)? {
-                                ManifestLine::CrateRow(crate_row) => {
-                                    cargo_crate_rows.push(crate_row);
-                                }
-                                ManifestLine::StackCount(stack_count) => {
-                                    cargo_crate_row_count = stack_count;
-                                }
-                                ManifestLine::MoveCommand(move_command) => {
-                                    move_commands.push(move_command);
-                                }
-                            },
-                            Rule::cargo_crate
-                            | Rule::cargo_crate_row
-                            | Rule::cargo_crate_stack
-                            | Rule::cargo_crate_stack_row
-                            | Rule::move_command => unreachable!(),
-                        }
+        for manifest_line in cargo_manifest_contents {
+            match manifest_line.as_rule() {
+                Rule::manifest_line => match parse_manifest_line(manifest_line).on_line(line_number, line)? {
+                    ManifestLine::CrateRow(crate_row) => {
+                        cargo_crate_rows.push((line_number, line, crate_row));
                     }
-                }
-                Err(err) => {
-                    return Err(Box::new(err));
-                }
+                    ManifestLine::StackRow(columns) => {
+                        stack_columns = columns;
+                    }
+                    ManifestLine::MoveCommand(move_command) => {
+                        move_commands.push(move_command.on_line(line_number, line));
+                    }
+                },
+                Rule::cargo_crate
+                | Rule::cargo_crate_label
+                | Rule::cargo_crate_row
+                | Rule::cargo_crate_stack
+                | Rule::cargo_crate_stack_row
+                | Rule::move_command
+                | Rule::EOI => unreachable!(),
             }
         }
     }
 
-    let cargo_bay = CargoBay::new(cargo_crate_row_count, cargo_crate_rows);
+    // The stack numbers are drawn below the crates, so crates can only be
+    // placed once all of the drawing has been read
+    let mut placed_rows = Vec::new();
+    let mut warnings = Vec::new();
+    for (line_number, line, crate_row) in cargo_crate_rows {
+        let (placed_row, warning) = place_crate_row(line_number, line, crate_row, &stack_columns)?;
+        placed_rows.push(placed_row);
+        warnings.extend(warning);
+    }
+    let cargo_bay = CargoBay::new(stack_columns.len(), placed_rows);
+
+    Ok((cargo_bay, move_commands, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok((cargo_bay, move_commands))
+    fn stacks(cargo_bay: &CargoBay) -> Vec<Vec<String>> {
+        cargo_bay
+            .heights()
+            .iter()
+            .enumerate()
+            .map(|(stack, height)| {
+                (0..*height)
+                    .map(|layer| String::from(cargo_bay.get(stack, layer).unwrap().id()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn places_crates_by_the_stack_number_row() {
+        let input = "         [12]\n[AB] [C]  [D]  [E]\n  1   2    3    4\n\nmove 2 from 3 to 4";
+        let (cargo_bay, _) = parse_cargo_bay_and_move_commands(input).unwrap();
+        assert_eq!(stacks(&cargo_bay), vec![vec!["AB"], vec!["C"], vec!["D", "12"], vec!["E"]]);
+
+        let err = parse_cargo_bay_and_move_commands("   [A]\n 1    2").unwrap_err();
+        assert!(format!("{}", err).starts_with("crate is not drawn above a stack number\n --> <input>:1:4"));
+        let err = parse_cargo_bay_and_move_commands("[A] [B]\n 1   3").unwrap_err();
+        assert!(format!("{}", err).starts_with("expected stack number 2\n --> <input>:2:6"));
+    }
+
+    #[test]
+    fn rejects_trailing_junk() {
+        for (input, line, column) in [
+            ("[Z] garbage\n 1 \n", 1, 5),
+            ("[Z]\n 1 \n\nmove 1 from 1 to 1 banana\n", 4, 19),
+            ("[Z]\n 1 \n\nmove 3 from 1 to 1xyz\n", 4, 19),
+        ] {
+            let err = format!("{}", parse_cargo_bay_and_move_commands(input).unwrap_err());
+            let caret = format!("\n  | {}^", " ".repeat(column - 1));
+            assert!(err.contains(&format!("<input>:{}:{}", line, column)), "{}", err);
+            assert!(err.contains(&caret), "{}", err);
+        }
+    }
 }
@@ -0,0 +1,331 @@
+//! Inverse planning: search for the fewest move commands that take a cargo bay
+//! to a target arrangement, or to a target set of top crates. The search is
+//! breadth first over bay states, crates with the same label being
+//! interchangeable, and gives up after a given number of states. States are
+//! kept compact, as the interned labels of every stack, rather than as bays.
+
+use std::collections::{ HashMap, HashSet };
+use std::rc::Rc;
+
+use aoc_common_lib::error::{ AocError, Result };
+
+use crate::crane::Crane;
+use crate::model::{ CargoBay, MoveCommand, MoveCommands };
+
+/// States explored before [`plan`] gives up when no limit is given.
+pub const DEFAULT_MAX_STATES: usize = 250_000;
+
+/// Where the crates should end up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// The labels on every stack, from the floor up.
+    Arrangement(Vec<Vec<String>>),
+    /// The labels of the top crates of the stacks that aren't empty, left to
+    /// right like the puzzle answer.
+    Top(Vec<String>),
+}
+
+impl Target {
+    /// The arrangement of `cargo_bay` as a target.
+    pub fn arrangement_of(cargo_bay: &CargoBay) -> Self {
+        Self::Arrangement(labels(cargo_bay))
+    }
+
+    /// Top crates written like the puzzle answer, `ZMN`, or with labels of
+    /// more than one character separated by commas or spaces, `AB,C`.
+    pub fn top(top: &str) -> Self {
+        let labels = if top.contains([',', ' ']) {
+            top.split([',', ' '])
+                .filter(|label| !label.is_empty())
+                .map(String::from)
+                .collect()
+        } else {
+            top.chars().map(String::from).collect()
+        };
+        Self::Top(labels)
+    }
+
+    /// Why the target can't be reached whatever the moves, when that can be
+    /// told without searching.
+    fn unreachable_because(&self, cargo_bay: &CargoBay) -> Option<String> {
+        let mut available: HashMap<&str, usize> = HashMap::new();
+        let stacks = labels(cargo_bay);
+        for label in stacks.iter().flatten() {
+            *available.entry(label).or_default() += 1;
+        }
+        let crate_count: usize = available.values().sum();
+
+        let wanted: Vec<&String> = match self {
+            Self::Arrangement(target_stacks) => {
+                if target_stacks.len() != cargo_bay.stack_count() {
+                    return Some(
+                        format!("the target has {} stacks, the bay {}", target_stacks.len(), cargo_bay.stack_count())
+                    );
+                }
+                if target_stacks.iter().flatten().count() != crate_count {
+                    return Some(String::from("the target doesn't hold the same crates as the bay"));
+                }
+                target_stacks.iter().flatten().collect()
+            }
+            Self::Top(top) => {
+                if top.len() > cargo_bay.stack_count() {
+                    return Some(
+                        format!("the target has {} top crates, the bay {} stacks", top.len(), cargo_bay.stack_count())
+                    );
+                }
+                if top.is_empty() && crate_count > 0 {
+                    return Some(String::from("the bay can't be left without crates"));
+                }
+                top.iter().collect()
+            }
+        };
+        for label in wanted.iter() {
+            match available.get_mut(label.as_str()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                }
+                Some(_) => {
+                    return Some(format!("the bay doesn't have enough crates labelled `{}`", label));
+                }
+                None => {
+                    return Some(format!("the bay has no crate labelled `{}`", label));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The labels on every stack of `cargo_bay`, from the floor up.
+fn labels(cargo_bay: &CargoBay) -> Vec<Vec<String>> {
+    cargo_bay
+        .heights()
+        .into_iter()
+        .enumerate()
+        .map(|(stack, height)| {
+            (0..height)
+                .filter_map(|layer| cargo_bay.get(stack, layer))
+                .map(|cargo_crate| String::from(cargo_crate.id()))
+                .collect()
+        })
+        .collect()
+}
+
+/// Crate labels numbered in the order they are first seen.
+#[derive(Debug, Default)]
+struct Interner {
+    ids: HashMap<String, u16>,
+}
+
+impl Interner {
+    fn intern(&mut self, label: &str) -> Result<u16> {
+        if let Some(id) = self.ids.get(label) {
+            return Ok(*id);
+        }
+        let id = u16::try_from(self.ids.len())
+            .map_err(|_| AocError::usage("A plan can tell at most 65536 crate labels apart"))?;
+        self.ids.insert(String::from(label), id);
+        Ok(id)
+    }
+
+    fn intern_stacks(&mut self, stacks: &[Vec<String>]) -> Result<Vec<Vec<u16>>> {
+        stacks
+            .iter()
+            .map(|stack| {
+                stack
+                    .iter()
+                    .map(|label| self.intern(label))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// The interned labels of every stack, each stack led by its height.
+type State = Rc<[u16]>;
+
+fn encode(stacks: &[Vec<u16>]) -> Result<State> {
+    let mut state = Vec::with_capacity(stacks.len() + stacks.iter().map(|stack| stack.len()).sum::<usize>());
+    for stack in stacks.iter() {
+        state.push(u16::try_from(stack.len()).map_err(|_| AocError::usage("A plan can't handle stacks this high"))?);
+        state.extend(stack.iter());
+    }
+    Ok(state.into())
+}
+
+fn decode(state: &[u16]) -> Vec<Vec<u16>> {
+    let mut stacks = Vec::new();
+    let mut rest = state;
+    while let Some((height, after)) = rest.split_first() {
+        let (stack, after) = after.split_at(*height as usize);
+        stacks.push(stack.to_vec());
+        rest = after;
+    }
+    stacks
+}
+
+/// A move as `(size, origin, destination)`.
+type Move = (usize, usize, usize);
+
+/// The target with its labels interned.
+enum Goal {
+    Arrangement(Vec<Vec<u16>>),
+    Top(Vec<u16>),
+}
+
+impl Goal {
+    fn is_reached(&self, stacks: &[Vec<u16>]) -> bool {
+        match self {
+            Goal::Arrangement(target_stacks) => stacks == target_stacks.as_slice(),
+            Goal::Top(top) => stacks.iter().filter_map(|stack| stack.last()).eq(top.iter()),
+        }
+    }
+}
+
+/// Search for the fewest moves with `crane` that take `cargo_bay` to `target`,
+/// exploring at most `max_states` bay states. An unreachable target, or one
+/// not reached within the limit, is [`AocError::Unsolvable`].
+pub fn plan(cargo_bay: &CargoBay, target: &Target, crane: &dyn Crane, max_states: usize) -> Result<MoveCommands> {
+    if let Some(reason) = target.unreachable_because(cargo_bay) {
+        return Err(AocError::Unsolvable(format!("The target can't be reached, {}", reason)));
+    }
+
+    let mut interner = Interner::default();
+    let start = interner.intern_stacks(&labels(cargo_bay))?;
+    let goal = match target {
+        Target::Arrangement(target_stacks) => Goal::Arrangement(interner.intern_stacks(target_stacks)?),
+        Target::Top(top) =>
+            Goal::Top(
+                top
+                    .iter()
+                    .map(|label| interner.intern(label))
+                    .collect::<Result<Vec<u16>>>()?
+            ),
+    };
+
+    // States in the order they were reached, which is the order a breadth
+    // first search explores them in, with the state each was reached from and
+    // the move that got there
+    let start = encode(&start)?;
+    let mut states: Vec<(State, usize, Option<Move>)> = vec![(start.clone(), 0, None)];
+    let mut seen: HashSet<State> = HashSet::from([start]);
+
+    let mut explored = 0;
+    while explored < states.len() {
+        let stacks = decode(&states[explored].0);
+        if goal.is_reached(&stacks) {
+            let mut move_commands = Vec::new();
+            let mut state = explored;
+            while let (_, parent, Some((size, origin, destination))) = &states[state] {
+                move_commands.push(MoveCommand::new(*size, *origin, *destination));
+                state = *parent;
+            }
+            move_commands.reverse();
+            return Ok(move_commands);
+        }
+
+        for origin in 0..stacks.len() {
+            for destination in (0..stacks.len()).filter(|destination| *destination != origin) {
+                for size in 1..=stacks[origin].len() {
+                    let mut next_stacks = stacks.clone();
+                    for lift in crane.lifts(size) {
+                        let split_at = next_stacks[origin].len() - lift;
+                        let lifted = next_stacks[origin].split_off(split_at);
+                        next_stacks[destination].extend(lifted);
+                    }
+                    let next_state = encode(&next_stacks)?;
+                    if !seen.insert(next_state.clone()) {
+                        continue;
+                    }
+                    if states.len() >= max_states {
+                        return Err(AocError::Unsolvable(format!("No plan found within {} states", max_states)));
+                    }
+                    states.push((next_state, explored, Some((size, origin, destination))));
+                }
+            }
+        }
+        explored += 1;
+    }
+
+    Err(AocError::Unsolvable(format!("The target can't be reached with crane {}", crane.name())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::{ CrateMover9000, CrateMover9001 };
+    use crate::model::CargoCrate;
+    use crate::parser::parse_cargo_bay_and_move_commands;
+    use crate::top_crates_after;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+    const CHALLENGE: &str = include_str!("../../input_advent_challenge.txt");
+
+    #[test]
+    fn plans_the_fewest_moves() {
+        let (cargo_bay, move_commands) = parse_cargo_bay_and_move_commands(EXAMPLE).unwrap();
+        let mut target_cargo_bay = cargo_bay.clone();
+        for move_command in move_commands.iter() {
+            target_cargo_bay.apply(move_command, &CrateMover9000).unwrap();
+        }
+
+        let target = Target::arrangement_of(&target_cargo_bay);
+        let planned = plan(&cargo_bay, &target, &CrateMover9001, DEFAULT_MAX_STATES).unwrap();
+        assert!(planned.len() <= move_commands.len());
+        assert_eq!(top_crates_after(&cargo_bay, &planned, &CrateMover9001).unwrap(), "CMZ");
+
+        let planned = plan(&cargo_bay, &Target::top("ZMN"), &CrateMover9000, DEFAULT_MAX_STATES).unwrap();
+        assert_eq!(planned.len(), 2);
+        assert_eq!(top_crates_after(&cargo_bay, &planned, &CrateMover9000).unwrap(), "ZMN");
+    }
+
+    #[test]
+    fn compares_top_crates_stack_by_stack() {
+        let cargo_crate_rows = vec![vec![Some(CargoCrate::new("AB")), Some(CargoCrate::new("C")), None]];
+        let cargo_bay = CargoBay::new(3, cargo_crate_rows);
+        assert_eq!(Target::top("AB, C"), Target::Top(vec![String::from("AB"), String::from("C")]));
+        assert!(plan(&cargo_bay, &Target::top("AB,C"), &CrateMover9000, 10).unwrap().is_empty());
+
+        // Reads `ABC` like the bay does, but there is no crate `A`
+        let err = plan(&cargo_bay, &Target::top("A,BC"), &CrateMover9000, 10).unwrap_err();
+        assert_eq!(
+            format!("{}", err),
+            "Unsolvable input: The target can't be reached, the bay has no crate labelled `A`"
+        );
+        let planned = plan(&cargo_bay, &Target::top("C,AB"), &CrateMover9000, 10).unwrap();
+        assert_eq!(format!("{}", planned[0]), "move 1 from 1 to 3");
+        assert_eq!(planned.len(), 1);
+    }
+
+    #[test]
+    fn reports_unreachable_targets() {
+        let (cargo_bay, _) = parse_cargo_bay_and_move_commands(EXAMPLE).unwrap();
+        let missing_crate = Target::Arrangement(vec![vec![String::from("Z")], Vec::new(), Vec::new()]);
+        let err = plan(&cargo_bay, &missing_crate, &CrateMover9000, DEFAULT_MAX_STATES).unwrap_err();
+        assert_eq!(
+            format!("{}", err),
+            "Unsolvable input: The target can't be reached, the target doesn't hold the same crates as the bay"
+        );
+
+        let err = plan(&cargo_bay, &Target::top("ZZ"), &CrateMover9000, DEFAULT_MAX_STATES).unwrap_err();
+        assert_eq!(
+            format!("{}", err),
+            "Unsolvable input: The target can't be reached, the bay doesn't have enough crates labelled `Z`"
+        );
+        let err = plan(&cargo_bay, &Target::top("NMPZ"), &CrateMover9000, DEFAULT_MAX_STATES).unwrap_err();
+        assert_eq!(
+            format!("{}", err),
+            "Unsolvable input: The target can't be reached, the target has 4 top crates, the bay 3 stacks"
+        );
+        let err = plan(&cargo_bay, &Target::top("ZMN"), &CrateMover9000, 2).unwrap_err();
+        assert_eq!(format!("{}", err), "Unsolvable input: No plan found within 2 states");
+
+        // Told up front, without searching the challenge's huge state space
+        let (cargo_bay, _) = parse_cargo_bay_and_move_commands(CHALLENGE).unwrap();
+        let err = plan(&cargo_bay, &Target::top("11111"), &CrateMover9000, DEFAULT_MAX_STATES).unwrap_err();
+        assert_eq!(
+            format!("{}", err),
+            "Unsolvable input: The target can't be reached, the bay has no crate labelled `1`"
+        );
+    }
+}
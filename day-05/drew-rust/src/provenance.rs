@@ -0,0 +1,193 @@
+//! Crate provenance: where each crate started, which moves carried it and
+//! where it ended up. Crates are told apart by their serial since labels can
+//! repeat. Trails are kept beside the bay rather than in each crate so that the
+//! bay stays cheap to clone for replays and undo.
+
+use std::{ collections::HashMap, fmt::Write };
+
+use aoc_common_lib::error::Result;
+
+use crate::crane::Crane;
+use crate::model::{ CargoBay, MoveCommand };
+
+/// `(stack, layer)` of a crate, both indexed from 0 with layer 0 the floor.
+pub type Position = (usize, usize);
+
+/// A crate being carried by the move at `step`, counted from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Movement {
+    pub step: usize,
+    pub from: Position,
+    pub to: Position,
+}
+
+fn describe(position: Position) -> String {
+    format!("stack {} layer {}", position.0 + 1, position.1 + 1)
+}
+
+/// A cargo bay that records the trail of every crate moved.
+#[derive(Debug, Clone)]
+pub struct Provenance {
+    cargo_bay: CargoBay,
+    move_commands: Vec<MoveCommand>,
+    /// Label, starting position and movements of each crate, by serial.
+    labels: Vec<String>,
+    starts: Vec<Position>,
+    trails: Vec<Vec<Movement>>,
+}
+
+impl Provenance {
+    pub fn new(cargo_bay: CargoBay) -> Self {
+        let mut crates = Vec::new();
+        for (stack, height) in cargo_bay.heights().into_iter().enumerate() {
+            for layer in 0..height {
+                if let Some(cargo_crate) = cargo_bay.get(stack, layer) {
+                    crates.push((cargo_crate.serial(), String::from(cargo_crate.id()), (stack, layer)));
+                }
+            }
+        }
+        crates.sort_by_key(|(serial, _, _)| *serial);
+
+        Self {
+            trails: vec![Vec::new(); crates.len()],
+            starts: crates.iter().map(|(_, _, position)| *position).collect(),
+            labels: crates.into_iter().map(|(_, label, _)| label).collect(),
+            cargo_bay,
+            move_commands: Vec::new(),
+        }
+    }
+
+    /// Apply every move command in turn.
+    pub fn record(cargo_bay: CargoBay, move_commands: &[MoveCommand], crane: &dyn Crane) -> Result<Self> {
+        let mut provenance = Self::new(cargo_bay);
+        for move_command in move_commands.iter() {
+            provenance.apply(move_command, crane)?;
+        }
+        Ok(provenance)
+    }
+
+    /// Apply a move and note where each crate it carried was put down.
+    pub fn apply(&mut self, move_command: &MoveCommand, crane: &dyn Crane) -> Result<()> {
+        let (origin, destination) = (move_command.origin(), move_command.destination());
+        let heights = self.cargo_bay.heights();
+        let origin_height = heights.get(origin).copied().unwrap_or(0);
+        let moved = origin_height.min(move_command.size());
+        let lifted_from: HashMap<usize, Position> = (origin_height - moved..origin_height)
+            .filter_map(|layer| {
+                self.cargo_bay.get(origin, layer).map(|cargo_crate| (cargo_crate.serial(), (origin, layer)))
+            })
+            .collect();
+
+        self.cargo_bay.apply(move_command, crane)?;
+        self.move_commands.push(move_command.clone());
+        let step = self.move_commands.len();
+
+        let destination_height = self.cargo_bay.heights()[destination];
+        for layer in destination_height - moved..destination_height {
+            if let Some(cargo_crate) = self.cargo_bay.get(destination, layer) {
+                let movement = Movement { step, from: lifted_from[&cargo_crate.serial()], to: (destination, layer) };
+                self.trails[cargo_crate.serial()].push(movement);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The bay after the moves applied so far.
+    pub fn cargo_bay(&self) -> &CargoBay {
+        &self.cargo_bay
+    }
+
+    /// Serials of the crates labelled `label`.
+    pub fn serials_labelled(&self, label: &str) -> Vec<usize> {
+        (0..self.labels.len()).filter(|serial| self.labels[*serial] == label).collect()
+    }
+
+    pub fn start(&self, serial: usize) -> Option<Position> {
+        self.starts.get(serial).copied()
+    }
+
+    /// Where the crate is now.
+    pub fn position(&self, serial: usize) -> Option<Position> {
+        match self.trails.get(serial)?.last() {
+            Some(movement) => Some(movement.to),
+            None => self.start(serial),
+        }
+    }
+
+    /// Every move that carried the crate, in order.
+    pub fn trail(&self, serial: usize) -> &[Movement] {
+        self.trails.get(serial).map(|trail| trail.as_slice()).unwrap_or_default()
+    }
+
+    /// The moves that took crates from or put crates on `stack`, with their
+    /// step counted from 1.
+    pub fn moves_touching(&self, stack: usize) -> Vec<(usize, &MoveCommand)> {
+        (1..)
+            .zip(self.move_commands.iter())
+            .filter(|(_, move_command)| move_command.origin() == stack || move_command.destination() == stack)
+            .collect()
+    }
+
+    /// Where the crate started, each move that carried it and where it ended up.
+    pub fn trail_report(&self, serial: usize) -> String {
+        let (start, position) = match (self.start(serial), self.position(serial)) {
+            (Some(start), Some(position)) => (start, position),
+            _ => {
+                return format!("There is no crate #{}\n", serial);
+            }
+        };
+        let mut report = format!("[{}] #{} started on {}\n", self.labels[serial], serial, describe(start));
+        for movement in self.trail(serial) {
+            let _ = writeln!(
+                report,
+                "  move {}: {}, {} -> {}",
+                movement.step,
+                self.move_commands[movement.step - 1],
+                describe(movement.from),
+                describe(movement.to)
+            );
+        }
+        let _ = writeln!(report, "  ended on {}", describe(position));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::{ CrateMover9000, CrateMover9001 };
+    use crate::parser::parse_cargo_bay_and_move_commands;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn follows_crates_through_moves() {
+        let (cargo_bay, move_commands) = parse_cargo_bay_and_move_commands(EXAMPLE).unwrap();
+        let provenance = Provenance::record(cargo_bay.clone(), &move_commands, &CrateMover9000).unwrap();
+
+        let z = provenance.serials_labelled("Z");
+        assert_eq!(z, vec![0]);
+        assert_eq!(provenance.trail_report(0), [
+            "[Z] #0 started on stack 1 layer 1",
+            "  move 2: move 3 from 1 to 3, stack 1 layer 1 -> stack 3 layer 4",
+            "  ended on stack 3 layer 4",
+            "",
+        ].join("\n"));
+        assert_eq!(provenance.position(0), Some((2, 3)));
+        assert!(provenance.trail(provenance.serials_labelled("P")[0]).is_empty());
+        let steps: Vec<usize> = provenance
+            .moves_touching(2)
+            .iter()
+            .map(|(step, _)| *step)
+            .collect();
+        assert_eq!(steps, vec![2]);
+
+        // Every crate is where its trail says it is
+        let provenance = Provenance::record(cargo_bay, &move_commands, &CrateMover9001).unwrap();
+        for serial in 0..6 {
+            let (stack, layer) = provenance.position(serial).unwrap();
+            assert_eq!(provenance.cargo_bay().get(stack, layer).unwrap().serial(), serial);
+        }
+    }
+}
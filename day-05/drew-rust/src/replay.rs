@@ -0,0 +1,132 @@
+//! Step by step replay of a move list, rendering the bay after every move with
+//! the crates that were just moved highlighted.
+
+use std::fmt::Write;
+
+use aoc_common_lib::error::Result;
+
+use crate::crane::Crane;
+use crate::model::{ CargoBay, CargoCrate, MoveCommand };
+
+/// How the crates moved by a step stand out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// Reverse video, for animating in a terminal.
+    Ansi,
+    /// `(X)` instead of `[X]`, for plain text frames.
+    Parentheses,
+}
+
+impl Highlight {
+    fn render(&self, cargo_crate: &CargoCrate) -> String {
+        match self {
+            Highlight::Ansi => format!("\x1b[7m{}\x1b[0m", cargo_crate),
+            Highlight::Parentheses => format!("({})", cargo_crate.id()),
+        }
+    }
+}
+
+/// A single step of a replay. Step 0 is the bay before any move.
+#[derive(Debug, Clone)]
+pub struct Step<'a> {
+    pub number: usize,
+    pub move_command: Option<&'a MoveCommand>,
+    /// `(stack, layer)` of every crate the move put down, indexed from 0.
+    pub moved: Vec<(usize, usize)>,
+}
+
+/// Apply `move_commands` to `cargo_bay` one at a time, calling `on_step` with
+/// the bay before the first move and after every move.
+pub fn replay<'a, F>(
+    cargo_bay: &mut CargoBay,
+    move_commands: &'a [MoveCommand],
+    crane: &dyn Crane,
+    mut on_step: F
+) -> Result<()> where F: FnMut(&Step<'a>, &CargoBay) -> Result<()> {
+    on_step(&(Step { number: 0, move_command: None, moved: Vec::new() }), cargo_bay)?;
+
+    for (number, move_command) in (1..).zip(move_commands.iter()) {
+        // Counted before the move, a move back onto its own stack leaves the
+        // heights as they were but still lifts the crates
+        let origin_height = cargo_bay.heights().get(move_command.origin()).copied().unwrap_or(0);
+        let moved_count = move_command.size().min(origin_height);
+        cargo_bay.apply(move_command, crane)?;
+        let height = cargo_bay.heights()[move_command.destination()];
+        let moved = (height - moved_count..height)
+            .map(|layer| (move_command.destination(), layer))
+            .collect();
+
+        on_step(&(Step { number, move_command: Some(move_command), moved }), cargo_bay)?;
+    }
+
+    Ok(())
+}
+
+/// Render a step: a heading naming the move followed by the bay with the
+/// moved crates highlighted.
+pub fn render_step(step: &Step, cargo_bay: &CargoBay, highlight: Highlight) -> String {
+    let mut rendered = match step.move_command {
+        Some(move_command) => format!("Move {}: {}\n", step.number, move_command),
+        None => String::from("Start:\n"),
+    };
+    let _ = cargo_bay.render_with(
+        &mut rendered,
+        &(|stack, layer, cargo_crate| {
+            if step.moved.contains(&(stack, layer)) {
+                highlight.render(cargo_crate)
+            } else {
+                format!("{}", cargo_crate)
+            }
+        })
+    );
+    let _ = writeln!(rendered);
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common_lib::error::AocError;
+    use crate::crane::CrateMover9001;
+    use crate::parser::parse_cargo_bay_and_move_commands;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn renders_every_step() {
+        let (mut cargo_bay, move_commands) = parse_cargo_bay_and_move_commands(EXAMPLE).unwrap();
+        let mut frames: Vec<String> = Vec::new();
+        replay(&mut cargo_bay, &move_commands, &CrateMover9001, |step, cargo_bay| {
+            frames.push(render_step(step, cargo_bay, Highlight::Parentheses));
+            Ok(())
+        }).unwrap();
+
+        assert_eq!(frames.len(), move_commands.len() + 1);
+        assert!(frames[0].starts_with("Start:\n    [D]    \n"));
+        let expected =
+            "Move 2: move 3 from 1 to 3\n        (D)\n        (N)\n    [C] (Z)\n    [M] [P]\n 0   1   2  \n\n";
+        assert_eq!(frames[2], expected);
+    }
+
+    #[test]
+    fn highlights_moves_onto_the_same_stack() {
+        let (mut cargo_bay, move_commands) = parse_cargo_bay_and_move_commands(
+            "[A]\n[B]\n[C]\n 1 \n\nmove 2 from 1 to 1\n"
+        ).unwrap();
+        let mut moved = Vec::new();
+        replay(&mut cargo_bay, &move_commands, &CrateMover9001, |step, _| {
+            moved = step.moved.clone();
+            Ok(())
+        }).unwrap();
+        assert_eq!(moved, vec![(0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn rejects_moves_from_missing_stacks() {
+        let (mut cargo_bay, move_commands) = parse_cargo_bay_and_move_commands(
+            "[A]    \n[B] [C]\n 1   2 \n\nmove 1 from 3 to 1\n"
+        ).unwrap();
+        let result = replay(&mut cargo_bay, &move_commands, &CrateMover9001, |_, _| Ok(()));
+        assert!(matches!(result, Err(AocError::InvalidCommand { .. })));
+    }
+}
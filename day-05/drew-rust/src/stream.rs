@@ -0,0 +1,164 @@
+//! Streaming manifests: the drawing of the stacks is read first and the move
+//! list is then parsed a line at a time, so moves can be applied as they are
+//! read and a move list of any length runs in constant memory.
+
+use std::io::BufRead;
+
+use aoc_common_lib::error::Result;
+
+use crate::crane::Crane;
+use crate::model::{ CargoBay, MoveCommand };
+use crate::parser::{ parse_manifest, parse_move_command_line };
+use crate::validate::{ check_move_command, ManifestWarning, Strictness };
+
+/// Move commands parsed one line at a time from the rest of a manifest.
+pub struct MoveCommandReader<R> {
+    reader: R,
+    line_number: usize,
+    line: String,
+    /// Moves that were read along with the drawing of the stacks.
+    pending: std::vec::IntoIter<MoveCommand>,
+}
+
+impl<R> Iterator for MoveCommandReader<R> where R: BufRead {
+    type Item = Result<MoveCommand>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(move_command) = self.pending.next() {
+            return Some(Ok(move_command));
+        }
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => {
+                    return None;
+                }
+                Ok(_) => (),
+                Err(err) => {
+                    return Some(Err(err.into()));
+                }
+            }
+            self.line_number += 1;
+            let line = self.line.trim_end_matches(['\n', '\r']);
+            if !line.trim().is_empty() {
+                return Some(parse_move_command_line(self.line_number, line));
+            }
+        }
+    }
+}
+
+/// Read the drawing of the stacks up to the first blank line after it and
+/// return the bay, the crates left out of it and a reader for the moves.
+pub fn read_cargo_bay<R>(
+    mut reader: R
+) -> Result<(CargoBay, Vec<ManifestWarning>, MoveCommandReader<R>)> where R: BufRead {
+    let mut drawing = String::new();
+    let mut line_number = 0;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_number += 1;
+        let is_blank = line.trim().is_empty();
+        if is_blank && !drawing.trim().is_empty() {
+            break;
+        }
+        // Blank lines before the drawing are kept so that line numbers match
+        drawing.push_str(if is_blank { "\n" } else { &line });
+    }
+
+    let (cargo_bay, move_commands, warnings) = parse_manifest(&drawing)?;
+    let move_command_reader = MoveCommandReader {
+        reader,
+        line_number,
+        line,
+        pending: move_commands.into_iter(),
+    };
+
+    Ok((cargo_bay, warnings, move_command_reader))
+}
+
+/// Apply moves as they are read, checking each one like
+/// [`check_move_commands`] does. In lenient mode each move that runs short is
+/// handed to `on_warning` as it is found rather than collected, so memory stays
+/// constant however many there are. Returns the number of moves applied.
+///
+/// [`check_move_commands`]: crate::validate::check_move_commands
+pub fn apply_move_commands<I, F>(
+    cargo_bay: &mut CargoBay,
+    move_commands: I,
+    crane: &dyn Crane,
+    strictness: Strictness,
+    mut on_warning: F
+) -> Result<usize> where I: Iterator<Item = Result<MoveCommand>>, F: FnMut(ManifestWarning) {
+    let mut count = 0;
+
+    for move_command in move_commands {
+        let move_command = move_command?;
+        if let Some(warning) = check_move_command(&cargo_bay.heights(), &move_command) {
+            match strictness {
+                Strictness::Strict => {
+                    return Err(warning.into());
+                }
+                Strictness::Lenient => on_warning(warning),
+            }
+        }
+        cargo_bay.apply(&move_command, crane)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::CrateMover9001;
+    use crate::generate::{ generate_manifest, GeneratorConfig };
+    use crate::{ top_crates_after, top_ids };
+    use crate::writer::to_manifest;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn applies_moves_as_they_are_read() {
+        let (mut cargo_bay, warnings, move_commands) = read_cargo_bay(EXAMPLE.as_bytes()).unwrap();
+        assert!(warnings.is_empty());
+        let count = apply_move_commands(&mut cargo_bay, move_commands, &CrateMover9001, Strictness::Strict, drop)
+            .unwrap();
+        assert_eq!((count, top_ids(&cargo_bay).as_str()), (4, "MCD"));
+
+        let config = GeneratorConfig { move_count: 5_000, ..GeneratorConfig::default() };
+        let (generated_cargo_bay, generated_move_commands) = generate_manifest(&config, 9).unwrap();
+        let manifest = to_manifest(&generated_cargo_bay, &generated_move_commands);
+        let (mut cargo_bay, _, move_commands) = read_cargo_bay(manifest.as_bytes()).unwrap();
+        apply_move_commands(&mut cargo_bay, move_commands, &CrateMover9001, Strictness::Strict, drop).unwrap();
+        assert_eq!(
+            top_ids(&cargo_bay),
+            top_crates_after(&generated_cargo_bay, &generated_move_commands, &CrateMover9001).unwrap()
+        );
+    }
+
+    #[test]
+    fn reports_moves_on_their_lines() {
+        let input = "\n[A]\n 1   2\n\nmove 1 from 1 to 2\n\nmove 2 from 2 to 1\nmove 1 from 0 to 1\n";
+        let (mut cargo_bay, _, move_commands) = read_cargo_bay(input.as_bytes()).unwrap();
+        let move_commands: Vec<Result<MoveCommand>> = move_commands.collect();
+        assert_eq!(move_commands[1].as_ref().unwrap().line_number(), Some(7));
+        assert!(format!("{}", move_commands[2].as_ref().unwrap_err()).contains("<input>:8:"));
+
+        let mut warnings = Vec::new();
+        let count = apply_move_commands(
+            &mut cargo_bay,
+            move_commands.into_iter().take(2),
+            &CrateMover9001,
+            Strictness::Lenient,
+            |warning| warnings.push(warning)
+        ).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(warnings[0].line_number(), Some(7));
+    }
+}
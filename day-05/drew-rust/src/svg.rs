@@ -0,0 +1,179 @@
+//! SVG drawings of a cargo bay and a self-contained HTML report that steps
+//! through a replay with a slider. Crates are coloured by the first character
+//! of their label so that crates with the same letter are easy to follow.
+
+use std::fmt::Write;
+
+use aoc_common_lib::error::Result;
+
+use crate::crane::Crane;
+use crate::model::{ CargoBay, CargoCrate, MoveCommand };
+use crate::provenance::Position;
+use crate::replay::replay;
+
+/// Pixels per character of a crate label.
+const CHAR_WIDTH: usize = 10;
+/// Pixels from the floor of one layer to the next.
+const ROW_HEIGHT: usize = 28;
+/// Pixels between neighbouring stacks and around the drawing.
+const GAP: usize = 6;
+
+/// Fill colour for a crate, a hue picked from the first character of its label.
+fn fill(cargo_crate: &CargoCrate) -> String {
+    let first = cargo_crate.id().chars().next().unwrap_or('A');
+    let hue = match first {
+        'A'..='Z' => ((first as usize) - ('A' as usize)) * 360 / 26,
+        'a'..='z' => ((first as usize) - ('a' as usize)) * 360 / 26,
+        _ => ((first as usize) * 47) % 360,
+    };
+    format!("hsl({}, 65%, 72%)", hue)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Draw `cargo_bay` with room for at least `layers` crates on every stack,
+/// outlining the crates at the `highlighted` positions.
+fn draw(cargo_bay: &CargoBay, highlighted: &[Position], layers: usize, cell_width: usize) -> String {
+    let max_height = cargo_bay.heights().into_iter().max().unwrap_or(0).max(layers);
+    let crate_width = cell_width * CHAR_WIDTH;
+    let width = cargo_bay.stack_count() * (crate_width + GAP) + GAP;
+    let height = (max_height + 1) * ROW_HEIGHT + GAP;
+    let floor = max_height * ROW_HEIGHT + GAP;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"16\">\n"
+    );
+    for (stack, stack_height) in cargo_bay.heights().into_iter().enumerate() {
+        let x = GAP + stack * (crate_width + GAP);
+        for layer in 0..stack_height {
+            let cargo_crate = match cargo_bay.get(stack, layer) {
+                Some(cargo_crate) => cargo_crate,
+                None => {
+                    continue;
+                }
+            };
+            let y = floor - (layer + 1) * ROW_HEIGHT;
+            let stroke = if highlighted.contains(&(stack, layer)) {
+                "stroke=\"#000\" stroke-width=\"3\""
+            } else {
+                "stroke=\"#555\" stroke-width=\"1\""
+            };
+            let _ = writeln!(
+                svg,
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"3\" fill=\"{}\" {}/>",
+                x,
+                y + 1,
+                crate_width,
+                ROW_HEIGHT - 2,
+                fill(cargo_crate),
+                stroke
+            );
+            let _ = writeln!(
+                svg,
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+                x + crate_width / 2,
+                y + ROW_HEIGHT * 2 / 3,
+                escape(cargo_crate.id())
+            );
+        }
+        let _ = writeln!(
+            svg,
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#555\">{}</text>",
+            x + crate_width / 2,
+            floor + ROW_HEIGHT * 2 / 3,
+            stack + 1
+        );
+    }
+    let _ = writeln!(svg, "  <line x1=\"0\" y1=\"{floor}\" x2=\"{width}\" y2=\"{floor}\" stroke=\"#555\"/>");
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// An SVG drawing of the bay, one coloured box per crate above the stack
+/// numbers.
+pub fn render_svg(cargo_bay: &CargoBay) -> String {
+    draw(cargo_bay, &[], 0, cargo_bay.cell_width())
+}
+
+/// A standalone HTML page with a drawing of the bay before the first move and
+/// after every move, moved crates outlined, and a slider to step between them.
+/// Every drawing is sized to the tallest stack of the whole replay so the page
+/// doesn't jump about.
+pub fn render_report(
+    title: &str,
+    cargo_bay: &CargoBay,
+    move_commands: &[MoveCommand],
+    crane: &dyn Crane
+) -> Result<String> {
+    let mut frames: Vec<(String, CargoBay, Vec<Position>)> = Vec::new();
+    replay(&mut cargo_bay.clone(), move_commands, crane, |step, cargo_bay| {
+        let caption = match step.move_command {
+            Some(move_command) => format!("Move {}: {}", step.number, move_command),
+            None => String::from("Start"),
+        };
+        frames.push((caption, cargo_bay.clone(), step.moved.clone()));
+        Ok(())
+    })?;
+    let layers = frames
+        .iter()
+        .flat_map(|(_, cargo_bay, _)| cargo_bay.heights())
+        .max()
+        .unwrap_or(0);
+    let cell_width = frames
+        .iter()
+        .map(|(_, cargo_bay, _)| cargo_bay.cell_width())
+        .max()
+        .unwrap_or(3);
+
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(html, "<title>{}</title>", escape(title));
+    html.push_str("<style>.frame { display: none; } .frame.shown { display: block; } input { width: 100%; }</style>\n");
+    let _ = writeln!(html, "</head>\n<body>\n<h1>{} with crane {}</h1>", escape(title), escape(&crane.name()));
+    let _ = writeln!(html, "<input id=\"step\" type=\"range\" min=\"0\" max=\"{}\" value=\"0\">", frames.len() - 1);
+    for (number, (caption, frame_cargo_bay, moved)) in frames.iter().enumerate() {
+        let shown = if number == 0 { " shown" } else { "" };
+        let _ = writeln!(html, "<div class=\"frame{}\">\n<h2>{}</h2>", shown, escape(caption));
+        html.push_str(&draw(frame_cargo_bay, moved, layers, cell_width));
+        html.push_str("</div>\n");
+    }
+    html.push_str(
+        "<script>\n\
+         const frames = document.querySelectorAll('.frame');\n\
+         document.getElementById('step').addEventListener('input', (event) => {\n\
+         \x20 frames.forEach((frame, number) => frame.classList.toggle('shown', number == event.target.value));\n\
+         });\n\
+         </script>\n</body>\n</html>\n"
+    );
+
+    Ok(html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::CrateMover9000;
+    use crate::parser::parse_cargo_bay_and_move_commands;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn draws_every_crate_and_step() {
+        let (cargo_bay, move_commands) = parse_cargo_bay_and_move_commands(EXAMPLE).unwrap();
+        let svg = render_svg(&cargo_bay);
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert!(svg.contains(">Z</text>"));
+        assert!(svg.contains("fill=\"hsl(346, 65%, 72%)\""));
+
+        let html = render_report("Example <1>", &cargo_bay, &move_commands, &CrateMover9000).unwrap();
+        assert!(html.contains("<title>Example &lt;1&gt;</title>"));
+        assert_eq!(html.matches("<svg").count(), move_commands.len() + 1);
+        assert!(html.contains("<h2>Move 4: move 1 from 1 to 2</h2>"));
+        assert!(html.contains("max=\"4\""));
+        // Each step outlines the crates its move put down
+        assert_eq!(html.matches("stroke-width=\"3\"").count(), 1 + 3 + 2 + 1);
+    }
+}
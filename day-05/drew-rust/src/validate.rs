@@ -0,0 +1,230 @@
+//! Checks for manifests the crane can only carry out by bending the rules:
+//! crates drawn beyond the last numbered stack are left out of the bay and a
+//! move of more crates than a stack holds moves just the ones there are. In
+//! strict mode the first of these is an error, in lenient mode all of them are
+//! collected as warnings.
+
+use std::fmt;
+
+use aoc_common_lib::diagnostic::Location;
+use aoc_common_lib::error::{ AocError, ParseError, Result };
+
+use crate::model::{ CargoBay, MoveCommand, MoveCommands };
+use crate::parser::parse_manifest;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strictness {
+    /// Fail on the first issue.
+    Strict,
+    /// Carry on and report every issue.
+    #[default]
+    Lenient,
+}
+
+#[derive(Debug, Clone)]
+pub enum ManifestIssue {
+    /// Crates drawn to the right of the last numbered stack.
+    CratesBeyondStacks {
+        count: usize,
+        stack_count: usize,
+    },
+    /// A move of more crates than the origin stack holds, with the height of
+    /// every stack before the move.
+    ShortStack {
+        move_command: MoveCommand,
+        heights: Vec<usize>,
+    },
+}
+
+fn crates(count: usize) -> String {
+    if count == 1 { String::from("1 crate") } else { format!("{} crates", count) }
+}
+
+fn format_heights(heights: &[usize]) -> String {
+    heights
+        .iter()
+        .map(|height| height.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+impl ManifestIssue {
+    /// Why the crane can't do as it's told, without naming the command.
+    fn reason(&self) -> String {
+        match self {
+            Self::CratesBeyondStacks { count, stack_count } =>
+                format!("{} drawn beyond the last of {} stacks", crates(*count), stack_count),
+            Self::ShortStack { move_command, heights } =>
+                format!(
+                    "stack {} holds only {}, stack heights are {}",
+                    move_command.origin() + 1,
+                    crates(heights[move_command.origin()]),
+                    format_heights(heights)
+                ),
+        }
+    }
+}
+
+impl fmt::Display for ManifestIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CratesBeyondStacks { .. } => write!(f, "{}, left out of the bay", self.reason()),
+            Self::ShortStack { move_command, .. } => write!(f, "`{}` but {}", move_command, self.reason()),
+        }
+    }
+}
+
+/// A [`ManifestIssue`] and the line it was found on, when known.
+#[derive(Debug, Clone)]
+pub struct ManifestWarning {
+    pub issue: ManifestIssue,
+    pub location: Option<Location>,
+}
+
+impl ManifestWarning {
+    pub fn line_number(&self) -> Option<usize> {
+        self.location.as_ref().map(|location| location.line)
+    }
+}
+
+impl fmt::Display for ManifestWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "warning: {}", self.issue)?;
+        if let Some(location) = &self.location {
+            write!(f, "\n{}", location)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<ManifestWarning> for AocError {
+    fn from(warning: ManifestWarning) -> Self {
+        match &warning.issue {
+            ManifestIssue::CratesBeyondStacks { .. } => {
+                let parse_error = ParseError::new(&warning.issue.reason());
+                match warning.location {
+                    Some(location) => AocError::Parse(parse_error.located(location)),
+                    None => AocError::Parse(parse_error),
+                }
+            }
+            ManifestIssue::ShortStack { move_command, .. } =>
+                AocError::InvalidCommand {
+                    command: move_command.to_string(),
+                    reason: warning.issue.reason(),
+                    location: warning.location,
+                },
+        }
+    }
+}
+
+/// Warn when `move_command` moves more crates than its origin holds, given
+/// the stack `heights` before the move.
+pub fn check_move_command(heights: &[usize], move_command: &MoveCommand) -> Option<ManifestWarning> {
+    let height = match heights.get(move_command.origin()) {
+        Some(height) => *height,
+        None => {
+            return None;
+        }
+    };
+    if height >= move_command.size() {
+        return None;
+    }
+    let location = move_command.location().cloned();
+    let issue = ManifestIssue::ShortStack { move_command: move_command.clone(), heights: heights.to_vec() };
+    Some(ManifestWarning { issue, location })
+}
+
+/// Follow the stack heights through `move_commands` and warn about every move
+/// of more crates than its origin holds. Moves between stacks that don't exist
+/// are left for [`CargoBay::apply`] to refuse.
+pub fn check_move_commands(cargo_bay: &CargoBay, move_commands: &[MoveCommand]) -> Vec<ManifestWarning> {
+    let mut heights = cargo_bay.heights();
+    let mut warnings = Vec::new();
+
+    for move_command in move_commands.iter() {
+        let (origin, destination) = (move_command.origin(), move_command.destination());
+        if origin >= heights.len() || destination >= heights.len() {
+            continue;
+        }
+        warnings.extend(check_move_command(&heights, move_command));
+        let moved = heights[origin].min(move_command.size());
+        heights[origin] -= moved;
+        heights[destination] += moved;
+    }
+
+    warnings
+}
+
+/// Parse and check a manifest. In strict mode the first issue is returned as
+/// an error, in lenient mode every issue comes back as a warning in line order.
+pub fn check_manifest(
+    input: &str,
+    strictness: Strictness
+) -> Result<(CargoBay, MoveCommands, Vec<ManifestWarning>)> {
+    let (cargo_bay, move_commands, mut warnings) = parse_manifest(input)?;
+    warnings.extend(check_move_commands(&cargo_bay, &move_commands));
+    warnings.sort_by_key(|warning| warning.line_number());
+
+    match (strictness, warnings.first()) {
+        (Strictness::Strict, Some(warning)) => Err(warning.clone().into()),
+        _ => Ok((cargo_bay, move_commands, warnings)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    const SHORT: &str =
+        "    [D]        \n[N] [C]     [E]\n[Z] [M] [P] [F]\n 1   2   3 \n\nmove 3 from 2 to 1\nmove 4 from 3 to 1\n";
+
+    #[test]
+    fn example_has_no_issues() {
+        assert!(check_manifest(EXAMPLE, Strictness::Strict).unwrap().2.is_empty());
+    }
+
+    #[test]
+    fn reports_short_stacks_and_dropped_crates() {
+        let (cargo_bay, _, warnings) = check_manifest(SHORT, Strictness::Lenient).unwrap();
+        assert_eq!(cargo_bay.heights(), vec![2, 3, 1]);
+        let lines: Vec<Option<usize>> = warnings
+            .iter()
+            .map(|warning| warning.line_number())
+            .collect();
+        assert_eq!(lines, vec![Some(2), Some(3), Some(7)]);
+        let expected = [
+            "warning: 1 crate drawn beyond the last of 3 stacks, left out of the bay",
+            " --> <input>:2:13",
+            "  |",
+            "2 | [N] [C]     [E]",
+            "  |             ^^^",
+        ];
+        assert_eq!(format!("{}", warnings[0]), expected.join("\n"));
+        assert_eq!(
+            format!("{}", warnings[2].issue),
+            "`move 4 from 3 to 1` but stack 3 holds only 1 crate, stack heights are 5 0 1"
+        );
+
+        let err = check_manifest(SHORT, Strictness::Strict).unwrap_err();
+        assert!(format!("{}", err).starts_with("1 crate drawn beyond the last of 3 stacks\n --> <input>:2:13"));
+
+        let (cargo_bay, move_commands, _) = parse_manifest(&SHORT.replace("move 3", "move 4")).unwrap();
+        let err = AocError::from(check_move_commands(&cargo_bay, &move_commands).remove(0));
+        let expected = [
+            "Invalid command `move 4 from 2 to 1`: stack 2 holds only 3 crates, stack heights are 2 3 1",
+            " --> <input>:6:1",
+            "  |",
+            "6 | move 4 from 2 to 1",
+            "  | ^^^^^^^^^^^^^^^^^^",
+        ];
+        assert_eq!(format!("{}", err), expected.join("\n"));
+    }
+
+    #[test]
+    fn quotes_moves_as_they_were_written() {
+        let (_, _, warnings) = check_manifest(&SHORT.replace("move 4", "move 04"), Strictness::Lenient).unwrap();
+        assert!(format!("{}", warnings[2]).ends_with("7 | move 04 from 3 to 1\n  | ^^^^^^^^^^^^^^^^^^^"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Write a cargo bay and its move commands back out in the puzzle's manifest
+//! format, the inverse of [`parse_cargo_bay_and_move_commands`].
+//!
+//! [`parse_cargo_bay_and_move_commands`]: crate::parser::parse_cargo_bay_and_move_commands
+
+use std::fmt::{ self, Write };
+
+use crate::model::{ CargoBay, MoveCommand };
+
+/// Write the drawing of the stacks, the row of stack numbers, a blank line and
+/// one `move N from A to B` line per command. Every line of the drawing is as
+/// wide as the row of stack numbers, and every crate is drawn above the first
+/// digit of its stack number.
+pub fn write_manifest(f: &mut dyn Write, cargo_bay: &CargoBay, move_commands: &[MoveCommand]) -> fmt::Result {
+    let max_height = cargo_bay.heights().into_iter().max().unwrap_or(0);
+    let cell_width = cargo_bay.cell_width();
+
+    for layer in (0..max_height).rev() {
+        let cargo_crates: Vec<String> = (0..cargo_bay.stack_count())
+            .map(|stack| {
+                match cargo_bay.get(stack, layer) {
+                    Some(cargo_crate) => format!("{}{}", cargo_crate, " ".repeat(cell_width - cargo_crate.width())),
+                    None => " ".repeat(cell_width),
+                }
+            })
+            .collect();
+        writeln!(f, "{}", cargo_crates.join(" "))?;
+    }
+
+    let stack_numbers: Vec<String> = (1..=cargo_bay.stack_count())
+        .map(|stack| format!(" {:<width$}", stack, width = cell_width - 1))
+        .collect();
+    writeln!(f, "{}", stack_numbers.join(" "))?;
+    writeln!(f)?;
+
+    for move_command in move_commands.iter() {
+        writeln!(f, "{}", move_command)?;
+    }
+
+    Ok(())
+}
+
+/// The manifest [`write_manifest`] writes, as a string.
+pub fn to_manifest(cargo_bay: &CargoBay, move_commands: &[MoveCommand]) -> String {
+    let mut manifest = String::new();
+    let _ = write_manifest(&mut manifest, cargo_bay, move_commands);
+    manifest
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::model::CargoCrate;
+    use crate::parser::parse_cargo_bay_and_move_commands;
+
+    const CHALLENGE: &str = include_str!("../../input_advent_challenge.txt");
+
+    fn move_command_parts(move_commands: &[MoveCommand]) -> Vec<(usize, usize, usize)> {
+        move_commands
+            .iter()
+            .map(|move_command| (move_command.size(), move_command.origin(), move_command.destination()))
+            .collect()
+    }
+
+    #[test]
+    fn writes_the_puzzle_format() {
+        let (cargo_bay, move_commands) = parse_cargo_bay_and_move_commands(CHALLENGE).unwrap();
+        assert_eq!(to_manifest(&cargo_bay, &move_commands), CHALLENGE);
+    }
+
+    #[test]
+    fn writes_wide_crates_and_many_stacks() {
+        let cargo_crate_rows = vec![
+            vec![Some(CargoCrate::new("AB")), None, None, Some(CargoCrate::new("7"))],
+            vec![Some(CargoCrate::new("C")), Some(CargoCrate::new("DE")), None, Some(CargoCrate::new("F"))]
+        ];
+        let cargo_bay = CargoBay::new(10, cargo_crate_rows);
+        let move_commands = vec![MoveCommand::new(12, 9, 0)];
+        let expected = [
+            "[AB]           [7]                               ",
+            "[C]  [DE]      [F]                               ",
+            " 1    2    3    4    5    6    7    8    9    10 ",
+            "",
+            "move 12 from 10 to 1",
+            "",
+        ];
+        assert_eq!(to_manifest(&cargo_bay, &move_commands), expected.join("\n"));
+    }
+
+    /// Stacks of up to 9 crates with labels of up to 3 letters or digits on 1
+    /// to 12 stacks, drawn top row first.
+    fn cargo_bays() -> impl Strategy<Value = CargoBay> {
+        (1usize..=12)
+            .prop_flat_map(|stack_count| {
+                prop::collection::vec(prop::collection::vec("[A-Z0-9]{1,3}", 0..9), stack_count)
+            })
+            .prop_map(|stacks| {
+                let max_height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+                let cargo_crate_rows = (0..max_height)
+                    .rev()
+                    .map(|layer| {
+                        stacks
+                            .iter()
+                            .map(|stack| stack.get(layer).map(|id| CargoCrate::new(id)))
+                            .collect()
+                    })
+                    .collect();
+                CargoBay::new(stacks.len(), cargo_crate_rows)
+            })
+    }
+
+    fn manifests() -> impl Strategy<Value = (CargoBay, Vec<MoveCommand>)> {
+        cargo_bays().prop_flat_map(|cargo_bay| {
+            let stack_count = cargo_bay.stack_count();
+            let move_commands = prop::collection::vec((1usize..20, 0..stack_count, 0..stack_count), 0..10).prop_map(
+                |parts| {
+                    parts
+                        .into_iter()
+                        .map(|(size, origin, destination)| MoveCommand::new(size, origin, destination))
+                        .collect::<Vec<MoveCommand>>()
+                }
+            );
+            (Just(cargo_bay), move_commands)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn parse_write_parse_is_identity((cargo_bay, move_commands) in manifests()) {
+            let manifest = to_manifest(&cargo_bay, &move_commands);
+            let (parsed_cargo_bay, parsed_move_commands) = parse_cargo_bay_and_move_commands(&manifest).unwrap();
+            prop_assert_eq!(&parsed_cargo_bay, &cargo_bay);
+            prop_assert_eq!(move_command_parts(&parsed_move_commands), move_command_parts(&move_commands));
+            prop_assert_eq!(to_manifest(&parsed_cargo_bay, &parsed_move_commands), manifest);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use advent_day_6::baseline::determine_start_of_packet;
+use advent_day_6::window::find_marker;
+use advent_day_6::START_OF_MESSAGE_WIDTH;
+use criterion::{ criterion_group, criterion_main, Criterion, Throughput };
+
+/// A megabyte of thirteen letters repeated, so no start-of-message marker turns
+/// up until the last fourteen characters.
+fn long_datastream() -> String {
+    let mut datastream = "abcdefghijklm".repeat((1 << 20) / 13);
+    datastream.push_str("nopqrstuvwxyzA");
+    datastream
+}
+
+fn bench_window(c: &mut Criterion) {
+    let datastream = long_datastream();
+
+    let mut group = c.benchmark_group("start of message");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(datastream.len() as u64));
+    group.bench_function("baseline", |b| {
+        b.iter(|| determine_start_of_packet(datastream.chars(), START_OF_MESSAGE_WIDTH))
+    });
+    group.bench_function("window chars", |b| {
+        b.iter(|| find_marker(datastream.chars(), START_OF_MESSAGE_WIDTH).unwrap())
+    });
+    group.bench_function("window bytes", |b| {
+        b.iter(|| find_marker(datastream.bytes(), START_OF_MESSAGE_WIDTH).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_window);
+criterion_main!(benches);
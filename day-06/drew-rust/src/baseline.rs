@@ -0,0 +1,91 @@
+//! The first marker detector, which shifts the whole window and scans every
+//! count on each character. Kept as the baseline the constant time
+//! [`MarkerWindow`] is checked and benchmarked against.
+//!
+//! [`MarkerWindow`]: crate::window::MarkerWindow
+
+use std::collections::BTreeMap;
+
+#[derive(Debug)]
+struct ParseBuffer<T> {
+    size: usize,
+    data_buffer: BTreeMap<T, usize>,
+    data_stack: Vec<T>,
+}
+
+impl ParseBuffer<char> {
+    fn new(size: usize) -> Self {
+        ParseBuffer { size, data_buffer: BTreeMap::new(), data_stack: Vec::with_capacity(size) }
+    }
+
+    fn add(&mut self, next_character: char) {
+        if self.data_stack.len() < self.size {
+            // insert or increment the count
+            self.data_stack.insert(0, next_character);
+            match self.data_buffer.contains_key(&next_character) {
+                true => {
+                    let next_count = self.data_buffer.get(&next_character).unwrap() + 1;
+                    self.data_buffer.insert(next_character, next_count);
+                }
+                false => {
+                    self.data_buffer.insert(next_character, 1);
+                }
+            }
+        } else {
+            // Remove or decrement last key in map
+            let last_character = self.data_stack.pop().unwrap();
+            let last_count = match self.data_buffer.get(&last_character) {
+                Some(last_count) => last_count - 1,
+                None => 0,
+            };
+            match last_count {
+                0 => {
+                    self.data_buffer.remove(&last_character);
+                }
+                _ => {
+                    self.data_buffer.insert(last_character, last_count);
+                }
+            }
+
+            // Insert or increment the count
+            self.data_stack.insert(0, next_character);
+            match self.data_buffer.contains_key(&next_character) {
+                true => {
+                    let next_count = self.data_buffer.get(&next_character).unwrap() + 1;
+                    self.data_buffer.insert(next_character, next_count);
+                }
+                false => {
+                    self.data_buffer.insert(next_character, 1);
+                }
+            }
+        }
+    }
+
+    fn has_duplicates(&self) -> bool {
+        let max_count = *self.data_buffer.values().max().unwrap();
+        max_count > 1
+    }
+
+    fn is_start_of_packet(&mut self, next_character: &char) -> bool {
+        if self.data_stack.len() < self.size || self.has_duplicates() {
+            self.add(*next_character);
+            false
+        } else {
+            true
+        }
+    }
+}
+
+/// The index just past the first `buffer_width` distinct characters in a row,
+/// or the length of the datastream when there are none.
+pub fn determine_start_of_packet(datastream: std::str::Chars, buffer_width: usize) -> usize {
+    let mut parse_buffer: ParseBuffer<char> = ParseBuffer::new(buffer_width);
+    let mut index: usize = 0;
+    for character in datastream {
+        if parse_buffer.is_start_of_packet(&character) {
+            break;
+        }
+        index += 1;
+    }
+    index
+}
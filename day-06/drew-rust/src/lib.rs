@@ -0,0 +1,76 @@
+use aoc_common_lib::error::{ AocError, Result };
+use aoc_common_lib::solution::Solution;
+
+use crate::marker::{ first_markers, FirstMarker, MarkerKind };
+
+pub mod baseline;
+pub mod marker;
+pub mod window;
+
+/// The first marker of each kind on every line of the input, reading each line
+/// once for all the kinds.
+pub fn parse_message_stream(input: &str, kinds: &[MarkerKind]) -> Result<Vec<Vec<FirstMarker>>> {
+    input
+        .lines()
+        .map(|line| first_markers(line.chars(), kinds))
+        .collect()
+}
+
+pub const START_OF_PACKET_WIDTH: usize = 4;
+pub const START_OF_MESSAGE_WIDTH: usize = 14;
+
+/// Marker positions are reported per datastream line, separated by commas. A
+/// datastream without a marker has no answer.
+fn join_markers(datastreams: &[String], kind: MarkerKind) -> Result<String> {
+    let markers = (1..)
+        .zip(datastreams.iter())
+        .map(|(number, datastream)| {
+            match first_markers(datastream.chars(), &[kind])?[0] {
+                FirstMarker::Found(position) => Ok(format!("{}", position)),
+                FirstMarker::NotFound { .. } => {
+                    Err(AocError::Unsolvable(format!("No {} marker in datastream {}", kind, number)))
+                }
+            }
+        })
+        .collect::<Result<Vec<String>>>()?;
+    Ok(markers.join(","))
+}
+
+#[derive(Debug, Default)]
+pub struct TuningTrouble;
+
+impl Solution for TuningTrouble {
+    type Input = Vec<String>;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, datastreams: &Self::Input) -> Result<String> {
+        join_markers(datastreams, MarkerKind::StartOfPacket)
+    }
+
+    fn part2(&self, datastreams: &Self::Input) -> Result<String> {
+        join_markers(datastreams, MarkerKind::StartOfMessage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn solves_example() {
+        let datastreams = TuningTrouble.parse(EXAMPLE).unwrap();
+        assert_eq!(TuningTrouble.part1(&datastreams).unwrap(), "7,5,6,10,11");
+        assert_eq!(TuningTrouble.part2(&datastreams).unwrap(), "19,23,23,29,26");
+
+        let datastreams = TuningTrouble.parse("abcd\nabcabc\n").unwrap();
+        let err = TuningTrouble.part1(&datastreams).unwrap_err();
+        assert_eq!(format!("{}", err), "Unsolvable input: No start-of-packet marker in datastream 2");
+    }
+}
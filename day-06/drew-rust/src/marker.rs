@@ -0,0 +1,167 @@
+//! Every marker in a datastream, for several marker widths in a single pass.
+
+use std::collections::VecDeque;
+use std::fmt::{ self, Display, Formatter };
+
+use aoc_common_lib::error::Result;
+
+use crate::window::{ MarkerWindow, Symbol };
+use crate::{ START_OF_MESSAGE_WIDTH, START_OF_PACKET_WIDTH };
+
+/// What a marker announces, which decides how many distinct symbols it takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkerKind {
+    StartOfPacket,
+    StartOfMessage,
+    Custom(usize),
+}
+
+impl MarkerKind {
+    pub fn width(&self) -> usize {
+        match self {
+            MarkerKind::StartOfPacket => START_OF_PACKET_WIDTH,
+            MarkerKind::StartOfMessage => START_OF_MESSAGE_WIDTH,
+            MarkerKind::Custom(width) => *width,
+        }
+    }
+}
+
+impl Display for MarkerKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            MarkerKind::StartOfPacket => write!(f, "start-of-packet"),
+            MarkerKind::StartOfMessage => write!(f, "start-of-message"),
+            MarkerKind::Custom(width) => write!(f, "{}-wide marker", width),
+        }
+    }
+}
+
+/// A marker ending `position` symbols into the datastream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Marker {
+    pub kind: MarkerKind,
+    pub position: usize,
+}
+
+/// Where the first marker of a kind ends, if the datastream has one at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirstMarker {
+    Found(usize),
+    /// The whole datastream, `read` symbols long, was read without a marker.
+    NotFound { read: usize },
+}
+
+impl Display for FirstMarker {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FirstMarker::Found(position) => write!(f, "{}", position),
+            FirstMarker::NotFound { read } => write!(f, "not found in {} characters", read),
+        }
+    }
+}
+
+/// Every marker of every kind asked for, in the order they end. Markers of
+/// different kinds ending on the same symbol come in the order of the kinds.
+pub struct Markers<I, T> {
+    symbols: I,
+    windows: Vec<(MarkerKind, MarkerWindow<T>)>,
+    read: usize,
+    found: VecDeque<Marker>,
+}
+
+impl<I, T> Markers<I, T> where I: Iterator<Item = T>, T: Symbol {
+    pub fn new<S>(symbols: S, kinds: &[MarkerKind]) -> Result<Self> where S: IntoIterator<IntoIter = I> {
+        let windows = kinds
+            .iter()
+            .map(|kind| Ok((*kind, MarkerWindow::new(kind.width())?)))
+            .collect::<Result<Vec<(MarkerKind, MarkerWindow<T>)>>>()?;
+        Ok(Markers { symbols: symbols.into_iter(), windows, read: 0, found: VecDeque::new() })
+    }
+
+    /// Symbols read from the datastream so far.
+    pub fn read(&self) -> usize {
+        self.read
+    }
+}
+
+impl<I, T> Iterator for Markers<I, T> where I: Iterator<Item = T>, T: Symbol {
+    type Item = Marker;
+
+    fn next(&mut self) -> Option<Marker> {
+        loop {
+            if let Some(marker) = self.found.pop_front() {
+                return Some(marker);
+            }
+            let symbol = self.symbols.next()?;
+            self.read += 1;
+            for (kind, window) in self.windows.iter_mut() {
+                if window.push(symbol) {
+                    self.found.push_back(Marker { kind: *kind, position: self.read });
+                }
+            }
+        }
+    }
+}
+
+/// The first marker of each kind, in the order of `kinds`. The datastream is
+/// read once, and only as far as the last of the first markers.
+pub fn first_markers<T, S>(symbols: S, kinds: &[MarkerKind]) -> Result<Vec<FirstMarker>> where
+    T: Symbol,
+    S: IntoIterator<Item = T> {
+    let mut markers = Markers::new(symbols, kinds)?;
+    let mut firsts: Vec<Option<usize>> = vec![None; kinds.len()];
+    while firsts.iter().any(|first| first.is_none()) {
+        let marker = match markers.next() {
+            Some(marker) => marker,
+            None => {
+                break;
+            }
+        };
+        for (kind, first) in kinds.iter().zip(firsts.iter_mut()) {
+            if *kind == marker.kind && first.is_none() {
+                *first = Some(marker.position);
+            }
+        }
+    }
+
+    let read = markers.read();
+    Ok(
+        firsts
+            .into_iter()
+            .map(|first| {
+                match first {
+                    Some(position) => FirstMarker::Found(position),
+                    None => FirstMarker::NotFound { read },
+                }
+            })
+            .collect()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_every_marker_of_every_kind() {
+        let kinds = [MarkerKind::StartOfPacket, MarkerKind::Custom(2)];
+        let markers: Vec<(usize, usize)> = Markers::new("aabcdd".bytes(), &kinds)
+            .unwrap()
+            .map(|marker| (marker.kind.width(), marker.position))
+            .collect();
+        assert_eq!(markers, vec![(2, 3), (2, 4), (4, 5), (2, 5)]);
+
+        let kinds = [MarkerKind::StartOfPacket, MarkerKind::StartOfMessage, MarkerKind::Custom(3)];
+        let firsts = first_markers("mjqjpqmgbljsphdztnvjfqwrcgsmlb".chars(), &kinds).unwrap();
+        assert_eq!(firsts, vec![FirstMarker::Found(7), FirstMarker::Found(19), FirstMarker::Found(3)]);
+
+        let firsts = first_markers("abcabc".chars(), &kinds).unwrap();
+        assert_eq!(firsts, vec![
+            FirstMarker::NotFound { read: 6 },
+            FirstMarker::NotFound { read: 6 },
+            FirstMarker::Found(3),
+        ]);
+        assert_eq!(format!("{} {}", kinds[0], firsts[0]), "start-of-packet not found in 6 characters");
+        assert!(first_markers("abc".chars(), &[MarkerKind::Custom(0)]).is_err());
+    }
+}
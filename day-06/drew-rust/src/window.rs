@@ -0,0 +1,152 @@
+//! Marker detection in constant time per symbol. The window is a ring buffer
+//! over the last `width` symbols with a count for every possible symbol and a
+//! running total of the repeats in the window, so each step updates two counts
+//! and checking for a marker is a single comparison.
+
+use aoc_common_lib::error::{ AocError, Result };
+
+/// A symbol of a datastream, with a slot of its own in a count table.
+pub trait Symbol: Copy {
+    /// Number of slots the count table needs.
+    const ALPHABET_SIZE: usize;
+
+    fn index(self) -> usize;
+}
+
+impl Symbol for u8 {
+    const ALPHABET_SIZE: usize = 1 << 8;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Symbol for char {
+    /// Every Unicode scalar value. The table is allocated zeroed, so pages no
+    /// symbol touches are never mapped in.
+    const ALPHABET_SIZE: usize = (char::MAX as usize) + 1;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The last `width` symbols of a datastream.
+#[derive(Debug, Clone)]
+pub struct MarkerWindow<T> {
+    width: usize,
+    ring: Vec<T>,
+    /// Where the next symbol is written once the ring is full.
+    next: usize,
+    counts: Vec<u32>,
+    /// Occurrences in the window beyond the first of each symbol.
+    repeats: usize,
+}
+
+impl<T> MarkerWindow<T> where T: Symbol {
+    pub fn new(width: usize) -> Result<Self> {
+        if width == 0 {
+            return Err(AocError::usage("A marker is at least one character wide"));
+        }
+        if width > (u32::MAX as usize) {
+            return Err(AocError::usage("A marker can't be wider than 4294967295 characters"));
+        }
+        Ok(MarkerWindow {
+            width,
+            ring: Vec::with_capacity(width),
+            next: 0,
+            counts: vec![0; T::ALPHABET_SIZE],
+            repeats: 0,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Slide the window over the next symbol, dropping the oldest once the
+    /// window is full. Returns whether the window is now a marker.
+    pub fn push(&mut self, symbol: T) -> bool {
+        if self.ring.len() < self.width {
+            self.ring.push(symbol);
+        } else {
+            let oldest = std::mem::replace(&mut self.ring[self.next], symbol);
+            let count = &mut self.counts[oldest.index()];
+            if *count > 1 {
+                self.repeats -= 1;
+            }
+            *count -= 1;
+            self.next += 1;
+            if self.next == self.width {
+                self.next = 0;
+            }
+        }
+
+        let count = &mut self.counts[symbol.index()];
+        if *count > 0 {
+            self.repeats += 1;
+        }
+        *count += 1;
+
+        self.is_marker()
+    }
+
+    /// Whether the window is full and holds no symbol twice.
+    pub fn is_marker(&self) -> bool {
+        self.ring.len() == self.width && self.repeats == 0
+    }
+
+    /// Empty the window without giving up its count table.
+    pub fn clear(&mut self) {
+        for symbol in self.ring.drain(..) {
+            self.counts[symbol.index()] = 0;
+        }
+        self.next = 0;
+        self.repeats = 0;
+    }
+}
+
+/// The number of symbols read up to the end of the first `width` distinct
+/// symbols in a row, or `None` when the datastream has no such run.
+pub fn find_marker<T, I>(symbols: I, width: usize) -> Result<Option<usize>> where T: Symbol, I: IntoIterator<Item = T> {
+    let mut window = MarkerWindow::new(width)?;
+    for (read, symbol) in (1..).zip(symbols) {
+        if window.push(symbol) {
+            return Ok(Some(read));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::baseline;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn finds_the_same_markers_as_the_baseline() {
+        for datastream in EXAMPLE.lines().chain(["abcabcabc", "aaaa", "", "ab\u{e9}\u{1f980}ab"]) {
+            for width in 1..=14 {
+                let expected = baseline::determine_start_of_packet(datastream.chars(), width);
+                let marker = find_marker(datastream.chars(), width).unwrap();
+                assert_eq!(marker.unwrap_or(datastream.chars().count()), expected, "{} width {}", datastream, width);
+                if datastream.is_ascii() {
+                    assert_eq!(find_marker(datastream.bytes(), width).unwrap(), marker);
+                }
+            }
+        }
+        assert!(find_marker("abc".chars(), 0).is_err());
+    }
+
+    #[test]
+    fn slides_and_clears() {
+        let mut window: MarkerWindow<u8> = MarkerWindow::new(3).unwrap();
+        let markers: Vec<bool> = b"aabcbca".iter().map(|symbol| window.push(*symbol)).collect();
+        assert_eq!(markers, vec![false, false, false, true, false, false, true]);
+        window.clear();
+        assert!(!window.is_marker());
+        assert!(!window.push(b'a') && !window.push(b'b') && window.push(b'c'));
+    }
+}
@@ -0,0 +1,212 @@
+//! Persist a reconstructed [`Filesystem`] so that trees can be diffed across
+//! inputs or handed to other tools: as JSON (which can be loaded back), as an
+//! [ncdu export](https://dev.yorhel.nl/ncdu/jsonfmt) or as a `tar -tv` style
+//! listing.
+
+use std::fmt::Write;
+
+use serde::{ Deserialize, Serialize };
+use serde_json::{ json, Value };
+
+use aoc_common_lib::diagnostic::Location;
+use aoc_common_lib::error::{ ParseError, Result };
+
+use crate::model::{ Filesystem, NodePayload };
+
+/// A file or directory as it is written to JSON. Directory sizes are written
+/// for convenience but recomputed from the files when loading.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TreeEntry {
+    Directory {
+        name: String,
+        size: usize,
+        children: Vec<TreeEntry>,
+    },
+    File {
+        name: String,
+        size: usize,
+    },
+}
+
+impl TreeEntry {
+    fn from_node(filesystem: &Filesystem, node_id: usize) -> Option<Self> {
+        match filesystem.payload(node_id)? {
+            NodePayload::FilesystemLeaf(file) =>
+                Some(TreeEntry::File {
+                    name: String::from(file.name()),
+                    size: file.size(),
+                }),
+            NodePayload::FilesystemNode(directory) =>
+                Some(TreeEntry::Directory {
+                    name: String::from(if node_id == filesystem.root() { "/" } else { directory.name() }),
+                    size: directory.size(),
+                    children: filesystem
+                        .children_by_name(node_id)
+                        .into_iter()
+                        .filter_map(|child| TreeEntry::from_node(filesystem, child))
+                        .collect(),
+                }),
+        }
+    }
+
+    fn add_to(&self, filesystem: &mut Filesystem, directory: usize) {
+        match self {
+            TreeEntry::File { name, size } => {
+                filesystem.add_file(directory, name, *size);
+            }
+            TreeEntry::Directory { name, children, .. } => {
+                let directory = filesystem.add_directory(directory, name);
+                for child in children {
+                    child.add_to(filesystem, directory);
+                }
+            }
+        }
+    }
+}
+
+/// The whole tree as a [`TreeEntry`] rooted at `/`.
+pub fn to_tree_entry(filesystem: &Filesystem) -> TreeEntry {
+    TreeEntry::from_node(filesystem, filesystem.root()).unwrap_or(TreeEntry::Directory {
+        name: String::from("/"),
+        size: 0,
+        children: Vec::new(),
+    })
+}
+
+/// Pretty printed JSON of the whole tree.
+pub fn to_json(filesystem: &Filesystem) -> String {
+    serde_json::to_string_pretty(&to_tree_entry(filesystem)).unwrap_or_default()
+}
+
+/// Load a tree written by [`to_json`].
+pub fn from_json(json: &str) -> Result<Filesystem> {
+    let root: TreeEntry = match serde_json::from_str(json) {
+        Ok(root) => root,
+        Err(err) => {
+            let text = json.lines().nth(err.line().saturating_sub(1)).unwrap_or_default();
+            let location = Location::new(err.line(), text, err.column(), 1);
+            return Err(ParseError::new("invalid filesystem JSON").located(location).with_source(err).into());
+        }
+    };
+    let children = match root {
+        TreeEntry::Directory { children, .. } => children,
+        TreeEntry::File { .. } => {
+            return Err(ParseError::new("the root of a filesystem must be a directory").into());
+        }
+    };
+
+    let mut filesystem = Filesystem::new();
+    let root = filesystem.root();
+    for child in children.iter() {
+        child.add_to(&mut filesystem, root);
+    }
+    Ok(filesystem)
+}
+
+fn ncdu_entry(filesystem: &Filesystem, node_id: usize) -> Value {
+    match filesystem.payload(node_id) {
+        Some(NodePayload::FilesystemLeaf(file)) => {
+            json!({ "name": file.name(), "asize": file.size(), "dsize": file.size() })
+        }
+        Some(NodePayload::FilesystemNode(directory)) => {
+            let name = if node_id == filesystem.root() { "/" } else { directory.name() };
+            let mut entries = vec![json!({ "name": name })];
+            entries.extend(
+                filesystem
+                    .children_by_name(node_id)
+                    .into_iter()
+                    .map(|child| ncdu_entry(filesystem, child))
+            );
+            Value::Array(entries)
+        }
+        None => Value::Null,
+    }
+}
+
+/// The tree in ncdu's export format, which `ncdu -f` can browse. Directories
+/// are arrays of their own info followed by their entries.
+pub fn to_ncdu(filesystem: &Filesystem) -> String {
+    let metadata = json!({ "progname": env!("CARGO_PKG_NAME"), "progver": env!("CARGO_PKG_VERSION") });
+    json!([1, 0, metadata, ncdu_entry(filesystem, filesystem.root())]).to_string()
+}
+
+/// A `tar -tv` style listing of every file and directory below the root, with
+/// paths relative to the root.
+///
+/// ```text
+/// drwxr-xr-x root/root        0 1970-01-01 00:00 a/
+/// -rw-r--r-- root/root    29116 1970-01-01 00:00 a/f
+/// ```
+pub fn to_tar_listing(filesystem: &Filesystem) -> String {
+    let mut entries: Vec<(bool, usize, String)> = Vec::new();
+    collect_tar_entries(filesystem, filesystem.root(), &mut entries);
+    let width = entries
+        .iter()
+        .map(|(_, size, _)| size.to_string().len())
+        .max()
+        .unwrap_or(1);
+
+    let mut listing = String::new();
+    for (is_directory, size, path) in entries {
+        let mode = if is_directory { "drwxr-xr-x" } else { "-rw-r--r--" };
+        let _ = writeln!(listing, "{} root/root {:>width$} 1970-01-01 00:00 {}", mode, size, path, width = width);
+    }
+    listing
+}
+
+fn collect_tar_entries(filesystem: &Filesystem, directory: usize, entries: &mut Vec<(bool, usize, String)>) {
+    for child in filesystem.children_by_name(directory) {
+        let path = String::from(filesystem.path_of(child).trim_start_matches('/'));
+        match filesystem.payload(child) {
+            Some(NodePayload::FilesystemLeaf(file)) => entries.push((false, file.size(), path)),
+            Some(NodePayload::FilesystemNode(_)) => {
+                // tar stores directories themselves with no size
+                entries.push((true, 0, path));
+                collect_tar_entries(filesystem, child, entries);
+            }
+            None => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::disk_usage;
+    use crate::NoSpaceLeftOnDevice;
+    use aoc_common_lib::solution::Solution;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn round_trips_through_json() {
+        let filesystem = NoSpaceLeftOnDevice.parse(EXAMPLE).unwrap();
+        let json = to_json(&filesystem);
+        let loaded = from_json(&json).unwrap();
+        assert_eq!(disk_usage(&loaded), disk_usage(&filesystem));
+        assert_eq!(to_tree_entry(&loaded), to_tree_entry(&filesystem));
+        assert_eq!(to_json(&loaded), json);
+
+        let err = from_json("{\n  \"type\": \"folder\"\n}").unwrap_err();
+        assert!(format!("{}", err).starts_with("invalid filesystem JSON\n --> <input>:2:"));
+        assert!(from_json(r#"{ "type": "file", "name": "/", "size": 1 }"#).is_err());
+    }
+
+    #[test]
+    fn exports_ncdu_and_tar_listings() {
+        let filesystem = NoSpaceLeftOnDevice.parse(EXAMPLE).unwrap();
+        let ncdu: Value = serde_json::from_str(&to_ncdu(&filesystem)).unwrap();
+        assert_eq!(ncdu[0], 1);
+        assert_eq!(ncdu[3][0]["name"], "/");
+        assert_eq!(ncdu[3][1][0]["name"], "a");
+        assert_eq!(ncdu[3][2], json!({ "name": "b.txt", "asize": 14848514, "dsize": 14848514 }));
+
+        let listing = to_tar_listing(&filesystem);
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "drwxr-xr-x root/root        0 1970-01-01 00:00 a/");
+        assert_eq!(lines[2], "-rw-r--r-- root/root      584 1970-01-01 00:00 a/e/i");
+        assert_eq!(lines[6], "-rw-r--r-- root/root 14848514 1970-01-01 00:00 b.txt");
+    }
+}
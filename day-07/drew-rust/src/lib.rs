@@ -0,0 +1,138 @@
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+
+use aoc_common_lib::error::{ AocError, OnLine, Result };
+use aoc_common_lib::solution::Solution;
+
+use crate::model::Filesystem;
+use crate::parser::{ parse_transcript, ShellCommand, ShellDirectory, ShellFile, ShellLine, TranscriptLine };
+use crate::query::{ find, smallest_directory_to_free, FindQuery };
+
+pub mod export;
+pub mod model;
+pub mod parser;
+pub mod query;
+pub mod validate;
+
+pub const TOTAL_DISK_SPACE: usize = 70_000_000;
+pub const UPDATE_DISK_SPACE: usize = 30_000_000;
+pub const SMALL_DIRECTORY_SIZE: usize = 100_000;
+
+/// `cd` recovers from the mistakes [`validate_transcript`] warns about, going
+/// into directories that weren't listed and staying at the root on `cd ..`.
+///
+/// [`validate_transcript`]: crate::validate::validate_transcript
+fn handle_shell_command(
+    filesystem: &mut Filesystem,
+    current_directory: usize,
+    shell_command: &ShellCommand
+) -> Result<usize> {
+    match (shell_command.command(), shell_command.args()) {
+        // Only listing the current directory can be replayed, flags are ignored.
+        ("ls", args) if args.iter().all(|arg| arg.starts_with('-')) => Ok(current_directory),
+        ("ls", _) => Err(AocError::invalid_command("ls", "only the current directory can be listed")),
+        ("cd", [directory]) => Ok(filesystem.enter_directory(current_directory, directory)),
+        ("cd", _) => Err(AocError::invalid_command("cd", "expected a single directory")),
+        (command, _) => Err(AocError::invalid_command(command, "unknown command")),
+    }
+}
+
+fn handle_shell_directory(filesystem: &mut Filesystem, current_directory: usize, shell_directory: &ShellDirectory) {
+    filesystem.add_directory(current_directory, shell_directory.name());
+}
+
+fn handle_shell_file(filesystem: &mut Filesystem, current_directory: usize, shell_file: &ShellFile) {
+    filesystem.add_file(current_directory, shell_file.name(), shell_file.size());
+}
+
+/// Replay the `cd` and `ls` commands of a terminal session, building up the
+/// directory tree they explored. Commands that can't be replayed are reported
+/// on their line.
+pub fn convert_transcript_to_filesystem_tree(transcript: &[TranscriptLine]) -> Result<Filesystem> {
+    let mut filesystem = Filesystem::new();
+    let mut current_directory = filesystem.root();
+
+    for transcript_line in transcript.iter() {
+        match &transcript_line.shell_line {
+            ShellLine::ShellCommand(shell_command) => {
+                current_directory = handle_shell_command(&mut filesystem, current_directory, shell_command).on_line(
+                    transcript_line.line_number,
+                    transcript_line.text
+                )?;
+            }
+            ShellLine::ShellDirectory(shell_directory) => {
+                handle_shell_directory(&mut filesystem, current_directory, shell_directory);
+            }
+            ShellLine::ShellFile(shell_file) => {
+                handle_shell_file(&mut filesystem, current_directory, shell_file);
+            }
+        }
+    }
+
+    Ok(filesystem)
+}
+
+#[derive(Debug, Default)]
+pub struct NoSpaceLeftOnDevice;
+
+impl Solution for NoSpaceLeftOnDevice {
+    type Input = Filesystem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        convert_transcript_to_filesystem_tree(&parse_transcript(input)?)
+    }
+
+    /// Sum of the sizes of the directories holding at most 100000 bytes.
+    fn part1(&self, filesystem: &Self::Input) -> Result<usize> {
+        let query = FindQuery {
+            max_size: Some(SMALL_DIRECTORY_SIZE),
+            directories_only: true,
+            ..FindQuery::default()
+        };
+        Ok(
+            find(filesystem, &query)
+                .into_iter()
+                .map(|directory| filesystem.size_of(directory))
+                .sum::<usize>()
+        )
+    }
+
+    /// Size of the smallest directory that frees up enough space for the update.
+    fn part2(&self, filesystem: &Self::Input) -> Result<usize> {
+        let unused_space = TOTAL_DISK_SPACE.saturating_sub(filesystem.size_of(filesystem.root()));
+        let required_space = UPDATE_DISK_SPACE.saturating_sub(unused_space);
+        smallest_directory_to_free(filesystem, required_space)
+            .map(|directory| filesystem.size_of(directory))
+            .ok_or_else(|| AocError::unsolvable("no directory is large enough to free up space for the update"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    #[test]
+    fn solves_example() {
+        let filesystem = NoSpaceLeftOnDevice.parse(EXAMPLE).unwrap();
+        assert_eq!(filesystem.size_of(filesystem.root()), 48381165);
+        assert_eq!(NoSpaceLeftOnDevice.part1(&filesystem).unwrap(), 95437);
+        assert_eq!(NoSpaceLeftOnDevice.part2(&filesystem).unwrap(), 24933642);
+    }
+
+    #[test]
+    fn replays_past_the_issues_validation_warns_about() {
+        let filesystem = NoSpaceLeftOnDevice.parse("$ cd /\n$ cd ..\n$ ls\ndir a\n$ cd b\n$ ls\n10 c\n").unwrap();
+        assert_eq!(filesystem.path_of(filesystem.lookup("/b/c").unwrap()), "/b/c");
+    }
+
+    #[test]
+    fn reports_commands_that_cant_be_replayed_on_their_line() {
+        let err = NoSpaceLeftOnDevice.parse("$ cd /\n$ ls\ndir a\n$ ls a\n").unwrap_err();
+        assert!(format!("{}", err).contains("4 | $ ls a"), "{}", err);
+    }
+}
@@ -1,100 +1,143 @@
-extern crate r3bl_rs_utils;
-extern crate pest;
-#[macro_use]
-extern crate pest_derive;
+use advent_day_7::export::{ from_json, to_json, to_ncdu, to_tar_listing };
+use advent_day_7::model::Filesystem;
+use advent_day_7::parser::parse_transcript;
+use advent_day_7::query::{ directories_to_free, disk_usage, find, render_tree, smallest_directory_to_free, FindQuery };
+use advent_day_7::validate::validate_transcript;
+use advent_day_7::{ convert_transcript_to_filesystem_tree, NoSpaceLeftOnDevice };
+use aoc_common_lib::error::{ AocError, OnLine, Result };
+use aoc_common_lib::input::Input;
+use aoc_common_lib::solution::Solution;
+use clap::{ Parser, Subcommand, ValueEnum };
 
-mod model;
-mod parser;
-
-use std::path::Path;
-use std::{ env, error::Error };
-
-use aoc_common_lib::error::RuntimeError;
-use aoc_common_lib::utility::read_lines;
-use model::{ Tree, FilesystemNode, FilesystemLeaf };
-use parser::{ parse_shell_line, ShellLine };
-
-// Override the alias to use `Box<error::Error>`.
-type Result<T> = std::result::Result<T, Box<dyn Error>>;
-
-fn convert_shell_lines_to_filesystem_tree(filesystem_root: &mut FilesystemNode, lines: Vec<Option<ShellLine>>) {
-    let mut current_filesystem: &mut FilesystemNode = filesystem_root;
-
-    for shell_line in lines.iter() {
-        match shell_line {
-            Some(shell_line) =>
-                match shell_line {
-                    ShellLine::ShellCommand(shell_command) => {
-                        current_filesystem = handle_shell_command(current_filesystem, shell_command);
-                    }
-                    ShellLine::ShellDirectory(shell_directory) => {
-                        current_filesystem = handle_shell_directory(current_filesystem, shell_directory);
-                    }
-                    ShellLine::ShellFile(shell_file) => {
-                        current_filesystem = handle_shell_file(current_filesystem, shell_file);
-                    }
-                }
-            None => {
-                continue;
-            }
-        }
-    }
+#[derive(Parser)]
+#[command(about = "Replay a terminal session and answer questions about its filesystem")]
+struct Cli {
+    /// Terminal output to replay, `-` reads stdin
+    input: String,
+    /// The input is a tree exported as JSON rather than a terminal session
+    #[arg(long)]
+    json: bool,
+    /// Question to ask of the filesystem, the puzzle answers when omitted
+    #[command(subcommand)]
+    query: Option<Query>,
 }
 
-fn handle_shell_file<'a>(
-    current_filesystem: &'a mut FilesystemNode<'a>,
-    shell_file: &'a parser::ShellFile
-) -> &'a mut FilesystemNode<'a> {
-    // current_filesystem.add_file(FilesystemLeaf::new(shell_file.name(), shell_file.size()));
-    current_filesystem
+#[derive(Subcommand)]
+enum Query {
+    /// Total size of every directory
+    Du,
+    /// Files and directories matching every given criterion
+    Find {
+        /// Glob matched against names, e.g. `*.log`
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        min_size: Option<usize>,
+        #[arg(long)]
+        max_size: Option<usize>,
+        /// Only list directories
+        #[arg(long, conflicts_with = "files")]
+        directories: bool,
+        /// Only list files
+        #[arg(long)]
+        files: bool,
+    },
+    /// Render the directory tree with sizes
+    Tree,
+    /// Directories to delete to free up at least BYTES
+    Free {
+        bytes: usize,
+        /// Only delete a single directory, as the puzzle does
+        #[arg(long)]
+        single: bool,
+    },
+    /// Check the terminal session for inconsistencies, failing when there are any
+    Validate,
+    /// Write the whole tree out in another format
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+    },
 }
 
-fn handle_shell_directory<'a>(
-    current_filesystem: &'a mut FilesystemNode<'a>,
-    shell_directory: &'a parser::ShellDirectory
-) -> &'a mut FilesystemNode<'a> {
-    // current_filesystem.add_directory(&shell_directory.name());
-    current_filesystem
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    /// JSON that can be loaded back with `--json`
+    Json,
+    /// ncdu's export format, browse it with `ncdu -f`
+    Ncdu,
+    /// A `tar -tv` style listing
+    Tar,
 }
 
-fn handle_shell_command<'a>(
-    current_filesystem: &'a mut FilesystemNode<'a>,
-    shell_command: &'a parser::ShellCommand
-) -> &'a mut FilesystemNode<'a> {
-    current_filesystem
-}
-
-fn parse_spam(input_file_path: &str) -> Result<Vec<Option<ShellLine>>> {
-    let input_file = Path::new(input_file_path);
-    if !input_file.exists() {
-        let error_message = format!("Path {} does not appear to exist", input_file_path);
-        return Err(Box::new(RuntimeError::new(error_message)));
-    }
-    let mut shell_lines: Vec<Option<ShellLine>> = Vec::new();
-    if let Ok(lines) = read_lines(input_file) {
-        for line in lines {
-            match line {
-                Ok(line) => {
-                    shell_lines.push(parse_shell_line(&line));
-                }
-                Err(err) => {
-                    return Err(Box::new(err));
-                }
-            }
+fn free(filesystem: &Filesystem, bytes: usize, single: bool) -> Result<()> {
+    let directories = if single {
+        smallest_directory_to_free(filesystem, bytes).map(|directory| vec![directory])
+    } else {
+        directories_to_free(filesystem, bytes)
+    };
+    let directories = match directories {
+        Some(directories) => directories,
+        None => {
+            let used = filesystem.size_of(filesystem.root());
+            return Err(AocError::Unsolvable(format!("only {} bytes can be freed, short of {}", used, bytes)));
         }
+    };
+    for directory in directories.iter() {
+        println!("{}\t{}", filesystem.size_of(*directory), filesystem.path_of(*directory));
     }
-    Ok(shell_lines)
+    println!(
+        "{}\ttotal",
+        directories
+            .iter()
+            .map(|directory| filesystem.size_of(*directory))
+            .sum::<usize>()
+    );
+    Ok(())
 }
 
 fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        return Err(Box::new(RuntimeError::new(String::from("Must provide input file path"))));
+    let cli = Cli::parse();
+    let input = Input::open(&cli.input)?;
+    let filesystem = if cli.json {
+        from_json(input.as_str()).in_file(input.name())?
+    } else {
+        let transcript = parse_transcript(input.as_str()).in_file(input.name())?;
+        let mut warnings = validate_transcript(&transcript);
+        for warning in warnings.iter_mut() {
+            warning.location = warning.location.clone().in_file(input.name());
+            eprintln!("{}", warning);
+        }
+        if matches!(cli.query, Some(Query::Validate)) && !warnings.is_empty() {
+            let message = format!("{} inconsistencies in the terminal session", warnings.len());
+            return Err(AocError::inconsistent(&message));
+        }
+        convert_transcript_to_filesystem_tree(&transcript).in_file(input.name())?
+    };
+
+    match cli.query {
+        None => {
+            println!("{} total size of directories of at most 100000", NoSpaceLeftOnDevice.part1(&filesystem)?);
+            println!("{} size of the smallest directory to delete", NoSpaceLeftOnDevice.part2(&filesystem)?);
+        }
+        Some(Query::Du) => {
+            for (path, size) in disk_usage(&filesystem) {
+                println!("{}\t{}", size, path);
+            }
+        }
+        Some(Query::Find { name, min_size, max_size, directories, files }) => {
+            let query = FindQuery { name, min_size, max_size, directories_only: directories, files_only: files };
+            for node_id in find(&filesystem, &query) {
+                println!("{}\t{}", filesystem.size_of(node_id), filesystem.path_of(node_id));
+            }
+        }
+        Some(Query::Tree) => print!("{}", render_tree(&filesystem)),
+        Some(Query::Free { bytes, single }) => free(&filesystem, bytes, single)?,
+        Some(Query::Validate) => println!("The terminal session is consistent"),
+        Some(Query::Export { format: ExportFormat::Json }) => println!("{}", to_json(&filesystem)),
+        Some(Query::Export { format: ExportFormat::Ncdu }) => println!("{}", to_ncdu(&filesystem)),
+        Some(Query::Export { format: ExportFormat::Tar }) => print!("{}", to_tar_listing(&filesystem)),
     }
-    let input_path = &args[1];
-    let results = parse_spam(input_path)?;
-    let mut filesystem_root = Tree::new();
-    let filesystem = convert_shell_lines_to_filesystem_tree(&mut filesystem_root, results);
 
     Ok(())
-}
\ No newline at end of file
+}
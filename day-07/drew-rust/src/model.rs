@@ -1,127 +1,371 @@
-use core::marker::{ Send, Sync };
-use std::{ collections::BTreeMap, rc::Rc, sync::{ atomic::AtomicUsize, Arc } };
-use r3bl_rs_utils::{
-    tree_memory_arena::{ Arena, HasId, MTArena, ResultUidList },
-    utils::{ style_primary, style_prompt },
-};
-
-enum NodePayload {
-    FilesystemNode(FilesystemNode),
-    FilesystemLeaf(FilesystemLeaf),
-}
+use std::collections::BTreeMap;
+
+use aoc_common_lib::arena::Arena;
 
 #[derive(Debug, Clone)]
-struct Node {
-    parent: usize,
-    tree: Arc<&mut Tree>,
-    id: usize,
-    payload: NodePayload,
-    children: Vec<usize>,
+pub struct FilesystemLeaf {
+    name: String,
+    size: usize,
 }
 
-impl Node {
-    fn new(tree: Arc<&mut Tree>, id: usize, parent: usize, payload: NodePayload) -> Self {
-        let node = Self { parent, tree, id, payload, children: Vec::new() };
+impl FilesystemLeaf {
+    pub fn new(name: &str, size: usize) -> Self {
+        Self { name: String::from(name), size }
     }
 
-    pub fn add_directory(&mut self, path: &str) {
-        let node_id = self.tree.get_next_id();
-        let directory = FilesystemNode::new(self.id, path);
-        let directory_ref = Rc::new(directory);
-        let directory_node = Self::new(
-            Arc::clone(&self.tree),
-            node_id,
-            self.id,
-            NodePayload::FilesystemNode(directory)
-        );
-        self.insert(String::from(path), Rc::clone(&directory_ref));
-        self.tree.add_node(directory_node);
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
     }
 
-    pub fn add_file(&mut self, file_name: String, file_size: usize) {
-        let node_id = self.tree.get_next_id();
-        let file = FilesystemLeaf::new(self.id, file_name, file_size);
-        let file_ref = Rc::new(file);
-        let file_node = Self::new(Arc::clone(&self.tree), node_id, self.id, NodePayload::FilesystemLeaf(file));
-        self.files.insert(String::from(&file_leaf.name), file_leaf);
+    pub fn size(&self) -> usize {
+        self.size
     }
 }
 
-impl HasId for Node {
-    type Id = usize;
+/// A directory, its children are looked up by name and the total size of
+/// everything below it is kept up to date as files are added.
+#[derive(Debug, Clone)]
+pub struct FilesystemNode {
+    name: String,
+    size: usize,
+    directories: BTreeMap<String, usize>,
+    files: BTreeMap<String, usize>,
+}
+
+impl FilesystemNode {
+    pub fn root() -> Self {
+        Self::new("")
+    }
 
-    fn id(&self) -> &Self::Id {
-        &self.id
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            size: 0,
+            directories: BTreeMap::new(),
+            files: BTreeMap::new(),
+        }
     }
-}
 
-impl HasId for usize {
-    type Id = usize;
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
 
-    fn id(&self) -> &Self::Id {
-        self
+    /// Cached total size of the files in and below this directory.
+    pub fn size(&self) -> usize {
+        self.size
     }
-}
 
-struct Tree {
-    arena: Arena<Node>,
-    root_node: Option<Node>,
-    next_id: AtomicUsize,
-}
+    pub fn directory(&self, name: &str) -> Option<usize> {
+        self.directories.get(name).copied()
+    }
 
-impl Tree {
-    fn new() -> Self {
-        let mut tree = Self { arena: Arena::<usize>::new(), root_node: None, next_id: 1 };
-        let node = Node::new(Arc::new(&mut tree), 0, 0, NodePayload::FilesystemNode(FilesystemNode::root()));
-        tree.arena.add_new_node(node, None);
-        tree.root_node = Some(node);
-        tree
+    pub fn file(&self, name: &str) -> Option<usize> {
+        self.files.get(name).copied()
     }
 
-    fn get_next_id(&mut self) -> usize {
-        self.next_id.fetch_add(1, order)
+    /// Ids of the sub directories, ordered by name.
+    pub fn directories(&self) -> impl Iterator<Item = usize> + '_ {
+        self.directories.values().copied()
     }
 
-    fn add_node(&mut self, node: Node) -> usize {
-        self.arena.add_new_node(node, Some(node.parent))
+    /// Ids of the files, ordered by name.
+    pub fn files(&self) -> impl Iterator<Item = usize> + '_ {
+        self.files.values().copied()
     }
 }
 
-pub struct FilesystemLeaf {
-    parent: usize,
-    name: String,
-    size: usize,
+/// Payload of a node in the [`Filesystem`] arena, a directory or a file.
+#[derive(Debug, Clone)]
+pub enum NodePayload {
+    FilesystemNode(FilesystemNode),
+    FilesystemLeaf(FilesystemLeaf),
 }
 
-impl FilesystemLeaf {
-    pub fn new(parent: usize, name: String, size: usize) -> Self {
-        Self { parent, name, size }
+impl NodePayload {
+    pub fn name(&self) -> &str {
+        match self {
+            NodePayload::FilesystemNode(directory) => directory.name(),
+            NodePayload::FilesystemLeaf(file) => file.name(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            NodePayload::FilesystemNode(directory) => directory.size(),
+            NodePayload::FilesystemLeaf(file) => file.size(),
+        }
     }
 }
 
-pub struct FilesystemNode {
-    parent: usize,
-    path: String,
-    directories: BTreeMap<String, usize>,
-    files: BTreeMap<String, usize>,
+/// A directory tree held in the shared [`Arena`], nodes are referred to by
+/// their arena id.
+#[derive(Debug)]
+pub struct Filesystem {
+    arena: Arena<NodePayload>,
+    root: usize,
 }
 
-impl FilesystemNode {
-    pub fn root_node(parent: usize) -> Self {
-        Self {
-            parent,
-            path: String::from(""),
-            directories: BTreeMap::new(),
-            files: BTreeMap::new(),
+impl Filesystem {
+    pub fn new() -> Self {
+        let mut arena = Arena::new();
+        let root = arena.add_new_node(NodePayload::FilesystemNode(FilesystemNode::root()), None);
+        Self { arena, root }
+    }
+
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Call `f` with the payload of `node_id`, borrowed under its read lock.
+    pub fn with_payload<T, F>(&self, node_id: usize, f: F) -> Option<T> where F: FnOnce(&NodePayload) -> T {
+        let node = self.arena.get_node_arc(node_id)?;
+        let node = node.read().ok()?;
+        Some(f(&node.payload))
+    }
+
+    /// Call `f` with the directory `node_id`, `None` when it is a file.
+    pub fn with_directory<T, F>(&self, node_id: usize, f: F) -> Option<T> where F: FnOnce(&FilesystemNode) -> T {
+        self.with_payload(node_id, |payload| {
+            match payload {
+                NodePayload::FilesystemNode(directory) => Some(f(directory)),
+                NodePayload::FilesystemLeaf(_) => None,
+            }
+        })?
+    }
+
+    /// A copy of the payload of `node_id`. Copying a directory copies its
+    /// children, [`Filesystem::with_payload`] borrows instead.
+    pub fn payload(&self, node_id: usize) -> Option<NodePayload> {
+        self.with_payload(node_id, |payload| payload.clone())
+    }
+
+    /// A copy of the directory `node_id`, `None` when it is a file.
+    /// [`Filesystem::with_directory`] borrows instead.
+    pub fn directory(&self, node_id: usize) -> Option<FilesystemNode> {
+        self.with_directory(node_id, |directory| directory.clone())
+    }
+
+    pub fn is_directory(&self, node_id: usize) -> bool {
+        self.with_directory(node_id, |_| ()).is_some()
+    }
+
+    /// Name of a file or directory, empty for the root.
+    pub fn name_of(&self, node_id: usize) -> Option<String> {
+        self.with_payload(node_id, |payload| String::from(payload.name()))
+    }
+
+    fn update_directory<F>(&self, node_id: usize, update: F) where F: FnOnce(&mut FilesystemNode) {
+        if let Some(node) = self.arena.get_node_arc(node_id) {
+            if let Ok(mut node) = node.write() {
+                if let NodePayload::FilesystemNode(directory) = &mut node.payload {
+                    update(directory);
+                }
+            }
         }
     }
 
-    pub fn new(parent: usize, path: &str) -> Self {
-        Self {
-            parent,
-            path: String::from(path),
-            directories: BTreeMap::new(),
-            files: BTreeMap::new(),
+    pub fn parent_of(&self, node_id: usize) -> Option<usize> {
+        self.arena.get_parent_of(node_id)
+    }
+
+    /// Every ancestor of `node_id`, from its parent up to the root.
+    pub fn ancestors_of(&self, node_id: usize) -> Vec<usize> {
+        let mut ancestors = Vec::new();
+        let mut current = node_id;
+        while let Some(parent) = self.parent_of(current) {
+            ancestors.push(parent);
+            current = parent;
+        }
+        ancestors
+    }
+
+    /// Find the child of `directory` called `name`, directories first.
+    pub fn child_named(&self, directory: usize, name: &str) -> Option<usize> {
+        self.with_directory(directory, |directory| directory.directory(name).or_else(|| directory.file(name)))?
+    }
+
+    /// The files and sub directories of `directory` ordered by name, the way
+    /// the puzzle lists them.
+    pub fn children_by_name(&self, directory: usize) -> Vec<usize> {
+        let children: Vec<usize> = self
+            .with_directory(directory, |directory| directory.directories().chain(directory.files()).collect())
+            .unwrap_or_default();
+        let mut children: Vec<(String, usize)> = children
+            .into_iter()
+            .filter_map(|child| self.name_of(child).map(|name| (name, child)))
+            .collect();
+        children.sort();
+        children
+            .into_iter()
+            .map(|(_, child)| child)
+            .collect()
+    }
+
+    /// Where `cd path` leads from `directory`. Paths starting with `/` begin at
+    /// the root, `..` is the parent, `.` stays put and anything else is a sub
+    /// directory. `None` when there is no such directory.
+    pub fn change_directory(&self, directory: usize, path: &str) -> Option<usize> {
+        let mut current = if path.starts_with('/') { self.root } else { directory };
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            current = match name {
+                "." => current,
+                ".." => self.parent_of(current)?,
+                name => self.with_directory(current, |directory| directory.directory(name))??,
+            };
+        }
+        Some(current)
+    }
+
+    /// Where `cd path` leads from `directory` the way a shell takes it: `..` at
+    /// the root stays there and directories no `ls` has listed yet are added.
+    pub fn enter_directory(&mut self, directory: usize, path: &str) -> usize {
+        let mut current = if path.starts_with('/') { self.root } else { directory };
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            current = match name {
+                "." => current,
+                ".." => self.parent_of(current).unwrap_or(current),
+                name => self.add_directory(current, name),
+            };
+        }
+        current
+    }
+
+    /// Look up an absolute path such as `/a/e` or `/b.txt`.
+    pub fn lookup(&self, path: &str) -> Option<usize> {
+        let path = path.strip_prefix('/')?;
+        let mut current = self.root;
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            current = self.child_named(current, name)?;
         }
+        Some(current)
     }
-}
\ No newline at end of file
+
+    /// Absolute path of `node_id`, directories end in `/`.
+    pub fn path_of(&self, node_id: usize) -> String {
+        let mut names: Vec<String> = self
+            .ancestors_of(node_id)
+            .into_iter()
+            .rev()
+            .filter_map(|ancestor| self.name_of(ancestor))
+            .collect();
+        if let Some(name) = self.name_of(node_id) {
+            names.push(name);
+        }
+        if self.is_directory(node_id) {
+            format!("{}/", names.join("/"))
+        } else {
+            names.join("/")
+        }
+    }
+
+    /// Add a directory called `name` to `directory`, returning the existing one
+    /// when it has already been listed.
+    pub fn add_directory(&mut self, directory: usize, name: &str) -> usize {
+        if let Some(child) = self.with_directory(directory, |directory| directory.directory(name)).flatten() {
+            return child;
+        }
+        let child = self.arena.add_new_node(NodePayload::FilesystemNode(FilesystemNode::new(name)), Some(directory));
+        self.update_directory(directory, |directory| {
+            directory.directories.insert(String::from(name), child);
+        });
+        child
+    }
+
+    /// Add a file to `directory`, returning the existing one when it has
+    /// already been listed. The cached sizes of `directory` and all of its
+    /// ancestors grow by the size of a new file.
+    pub fn add_file(&mut self, directory: usize, name: &str, size: usize) -> usize {
+        if let Some(child) = self.with_directory(directory, |directory| directory.file(name)).flatten() {
+            return child;
+        }
+        let file = FilesystemLeaf::new(name, size);
+        let child = self.arena.add_new_node(NodePayload::FilesystemLeaf(file), Some(directory));
+        self.update_directory(directory, |directory| {
+            directory.files.insert(String::from(name), child);
+        });
+        for ancestor in self.ancestors_of(child) {
+            self.update_directory(ancestor, |directory| {
+                directory.size += size;
+            });
+        }
+        child
+    }
+
+    /// Every file and directory in the tree, including the root.
+    pub fn nodes(&self) -> Vec<usize> {
+        self.arena.tree_walk_dfs(self.root).unwrap_or_default()
+    }
+
+    /// Every directory in the tree, including the root.
+    pub fn directories(&self) -> Vec<usize> {
+        self.nodes()
+            .into_iter()
+            .filter(|node_id| self.is_directory(*node_id))
+            .collect()
+    }
+
+    /// Size of a file or the cached total size of a directory.
+    pub fn size_of(&self, node_id: usize) -> usize {
+        self.with_payload(node_id, |payload| payload.size()).unwrap_or(0)
+    }
+}
+
+impl Default for Filesystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn navigates_and_caches_sizes() {
+        let mut filesystem = Filesystem::new();
+        let root = filesystem.root();
+        let a = filesystem.add_directory(root, "a");
+        let e = filesystem.add_directory(a, "e");
+        assert_eq!(filesystem.add_directory(root, "a"), a);
+        let i = filesystem.add_file(e, "i", 584);
+        filesystem.add_file(a, "f", 29116);
+        filesystem.add_file(root, "b.txt", 14848514);
+        assert_eq!(filesystem.add_file(e, "i", 584), i);
+
+        assert_eq!(filesystem.size_of(e), 584);
+        assert_eq!(filesystem.size_of(a), 29700);
+        assert_eq!(filesystem.size_of(root), 14878214);
+
+        assert_eq!(filesystem.lookup("/a/e/i"), Some(i));
+        assert_eq!(filesystem.lookup("/a/e/"), Some(e));
+        assert_eq!(filesystem.lookup("/"), Some(root));
+        assert_eq!(filesystem.lookup("/a/x"), None);
+        assert_eq!(filesystem.path_of(e), "/a/e/");
+        assert_eq!(filesystem.path_of(i), "/a/e/i");
+        assert_eq!(filesystem.path_of(root), "/");
+
+        assert_eq!(filesystem.change_directory(e, ".."), Some(a));
+        assert_eq!(filesystem.change_directory(e, "/"), Some(root));
+        assert_eq!(filesystem.change_directory(root, ".."), None);
+        assert_eq!(filesystem.change_directory(root, "b.txt"), None);
+        assert_eq!(filesystem.change_directory(e, "../../a/./e"), Some(e));
+        assert_eq!(filesystem.change_directory(e, "/a"), Some(a));
+
+        assert_eq!(filesystem.enter_directory(root, "../a/e"), e);
+        let x = filesystem.enter_directory(e, "x");
+        assert_eq!(filesystem.path_of(x), "/a/e/x/");
+    }
+
+    #[test]
+    fn builds_wide_directories_without_copying_them() {
+        let mut filesystem = Filesystem::new();
+        let root = filesystem.root();
+        for index in 0..5_000 {
+            filesystem.add_file(root, &format!("f{}", index), index);
+        }
+        assert_eq!(filesystem.size_of(root), (0..5_000).sum::<usize>());
+        assert_eq!(filesystem.with_directory(root, |directory| directory.files().count()), Some(5_000));
+        assert_eq!(filesystem.with_directory(filesystem.lookup("/f7").unwrap(), |_| ()), None);
+        assert_eq!(filesystem.name_of(filesystem.children_by_name(root)[1]), Some(String::from("f1")));
+    }
+}
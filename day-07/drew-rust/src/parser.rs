@@ -1,14 +1,12 @@
-use std::error::Error;
+use pest::{ iterators::{ Pair, Pairs }, Parser };
 
-use pest::Parser;
-
-// Override the alias to use `Box<error::Error>`.
-type Result<T> = std::result::Result<T, Box<dyn Error>>;
+use aoc_common_lib::error::{ OnLine, ParseError, Result };
 
 #[derive(Parser)]
 #[grammar = "grammar.pest"] // relative to src
 pub struct ShellParser;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ShellCommand {
     command: String,
     args: Vec<String>,
@@ -28,6 +26,7 @@ impl ShellCommand {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ShellDirectory {
     name: String,
 }
@@ -37,11 +36,12 @@ impl ShellDirectory {
         Self { name }
     }
 
-    pub fn name(&self) -> String {
-        String::from(self.name)
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ShellFile {
     name: String,
     size: usize,
@@ -52,8 +52,8 @@ impl ShellFile {
         Self { name, size }
     }
 
-    pub fn name(&self) -> String {
-        String::from(&self.name)
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
     }
 
     pub fn size(&self) -> usize {
@@ -61,12 +61,202 @@ impl ShellFile {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ShellLine {
     ShellCommand(ShellCommand),
     ShellDirectory(ShellDirectory),
     ShellFile(ShellFile),
 }
 
-pub fn parse_shell_line(line: &str) -> Option<ShellLine> {
-    todo!()
-}
\ No newline at end of file
+/// Names run to the end of the line, trailing whitespace isn't part of them.
+fn parse_name(pair: Pair<Rule>) -> String {
+    String::from(pair.as_str().trim_end())
+}
+
+fn parse_command_line(mut command_line_pairs: Pairs<Rule>) -> Result<ShellLine> {
+    let mut command_pairs = match command_line_pairs.next() {
+        Some(pair) => pair.into_inner(),
+        None => {
+            return Err(ParseError::new("unexpected missing pair").into());
+        }
+    };
+    let pair = match command_pairs.next() {
+        Some(pair) => pair,
+        None => {
+            return Err(ParseError::new("unexpected missing pair").into());
+        }
+    };
+
+    let shell_command = match pair.as_rule() {
+        // The directory of a `cd` is the rest of the line, spaces included.
+        Rule::change_directory => {
+            let args = pair.into_inner().map(parse_name).collect();
+            ShellCommand::new(String::from("cd"), args)
+        }
+        Rule::command_name => {
+            let args = command_pairs.map(|pair| String::from(pair.as_str())).collect();
+            ShellCommand::new(String::from(pair.as_str()), args)
+        }
+        _ => unreachable!(),
+    };
+
+    Ok(ShellLine::ShellCommand(shell_command))
+}
+
+fn parse_result_line(result_line: Pair<Rule>, line_number: usize, line: &str) -> Result<ShellLine> {
+    let pair = match result_line.into_inner().next() {
+        Some(pair) => pair,
+        None => {
+            return Err(ParseError::new("unexpected missing pair").into());
+        }
+    };
+    match pair.as_rule() {
+        Rule::directory => {
+            let name = match pair.into_inner().next() {
+                Some(name) => parse_name(name),
+                None => {
+                    return Err(ParseError::new("unexpected missing pair").into());
+                }
+            };
+            Ok(ShellLine::ShellDirectory(ShellDirectory::new(name)))
+        }
+        Rule::file => {
+            let mut file_pairs = pair.into_inner();
+            let (size, name) = match (file_pairs.next(), file_pairs.next()) {
+                (Some(size), Some(name)) => (size.as_str(), parse_name(name)),
+                _ => {
+                    return Err(ParseError::new("unexpected missing pair").into());
+                }
+            };
+            let size = size.parse::<usize>().at_token(line_number, line, size)?;
+            Ok(ShellLine::ShellFile(ShellFile::new(name, size)))
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Parse a single line of the terminal output, either a `$` command or a
+/// line listed by `ls`.
+pub fn parse_shell_line(line_number: usize, line: &str) -> Result<ShellLine> {
+    let output_line = match ShellParser::parse(Rule::output_line, line).on_line(line_number, line)?.next() {
+        Some(pair) => pair,
+        None => {
+            return Err(ParseError::new("unexpected missing pair").into());
+        }
+    };
+
+    match output_line.into_inner().next() {
+        Some(pair) =>
+            match pair.as_rule() {
+                Rule::command_line => parse_command_line(pair.into_inner()),
+                Rule::result_line => parse_result_line(pair, line_number, line),
+                _ => unreachable!(),
+            }
+        None => Err(ParseError::new("unexpected missing pair").into()),
+    }
+}
+
+/// A parsed line of the terminal output along with where it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptLine<'a> {
+    pub line_number: usize,
+    pub text: &'a str,
+    pub shell_line: ShellLine,
+}
+
+/// Parse the whole terminal output keeping the line numbers and text of each
+/// line, skipping blank lines.
+pub fn parse_transcript(input: &str) -> Result<Vec<TranscriptLine<'_>>> {
+    let mut transcript = Vec::new();
+    for (line_number, text) in (1..).zip(input.lines()) {
+        if text.trim().is_empty() {
+            continue;
+        }
+        let shell_line = parse_shell_line(line_number, text)?;
+        transcript.push(TranscriptLine { line_number, text, shell_line });
+    }
+    Ok(transcript)
+}
+
+/// Parse the whole terminal output, skipping blank lines.
+pub fn parse_shell_lines(input: &str) -> Result<Vec<ShellLine>> {
+    Ok(
+        parse_transcript(input)?
+            .into_iter()
+            .map(|line| line.shell_line)
+            .collect()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    fn command(command: &str, args: &[&str]) -> ShellLine {
+        let args = args
+            .iter()
+            .map(|arg| String::from(*arg))
+            .collect();
+        ShellLine::ShellCommand(ShellCommand::new(String::from(command), args))
+    }
+
+    fn directory(name: &str) -> ShellLine {
+        ShellLine::ShellDirectory(ShellDirectory::new(String::from(name)))
+    }
+
+    fn file(size: usize, name: &str) -> ShellLine {
+        ShellLine::ShellFile(ShellFile::new(String::from(name), size))
+    }
+
+    #[test]
+    fn parses_example_transcript() {
+        let shell_lines = parse_shell_lines(EXAMPLE).unwrap();
+        assert_eq!(shell_lines, vec![
+            command("cd", &["/"]),
+            command("ls", &[]),
+            directory("a"),
+            file(14848514, "b.txt"),
+            file(8504156, "c.dat"),
+            directory("d"),
+            command("cd", &["a"]),
+            command("ls", &[]),
+            directory("e"),
+            file(29116, "f"),
+            file(2557, "g"),
+            file(62596, "h.lst"),
+            command("cd", &["e"]),
+            command("ls", &[]),
+            file(584, "i"),
+            command("cd", &[".."]),
+            command("cd", &[".."]),
+            command("cd", &["d"]),
+            command("ls", &[]),
+            file(4060174, "j"),
+            file(8033020, "d.log"),
+            file(5626152, "d.ext"),
+            file(7214296, "k")
+        ]);
+    }
+
+    #[test]
+    fn parses_real_world_names_and_flags() {
+        assert_eq!(parse_shell_line(1, "$ ls -la --color").unwrap(), command("ls", &["-la", "--color"]));
+        assert_eq!(parse_shell_line(1, "$ cd ../src-2/my dir ").unwrap(), command("cd", &["../src-2/my dir"]));
+        assert_eq!(parse_shell_line(1, "$\tcd\t/").unwrap(), command("cd", &["/"]));
+        assert_eq!(parse_shell_line(1, "dir node_modules.v2").unwrap(), directory("node_modules.v2"));
+        assert_eq!(parse_shell_line(1, "dir 2022").unwrap(), directory("2022"));
+        assert_eq!(parse_shell_line(1, "1024 Cargo (copy).tar.gz").unwrap(), file(1024, "Cargo (copy).tar.gz"));
+        assert_eq!(parse_shell_line(1, "12 .bashrc").unwrap(), file(12, ".bashrc"));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        let err = parse_shell_line(3, "12x b").unwrap_err();
+        assert!(format!("{}", err).contains("3 | 12x b"));
+        assert!(parse_shell_line(1, "dir").is_err());
+        assert!(parse_shell_line(1, "1024").is_err());
+        assert!(parse_shell_line(1, "$").is_err());
+    }
+}
@@ -0,0 +1,336 @@
+//! Questions that can be asked of a reconstructed [`Filesystem`], the puzzle's
+//! two answers are special cases of [`find`] and [`directories_to_free`].
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::model::{ Filesystem, NodePayload };
+
+/// `du` style totals: the path and size of every directory, ordered by path.
+pub fn disk_usage(filesystem: &Filesystem) -> Vec<(String, usize)> {
+    let mut usage: Vec<(String, usize)> = filesystem
+        .directories()
+        .into_iter()
+        .map(|directory| (filesystem.path_of(directory), filesystem.size_of(directory)))
+        .collect();
+    usage.sort();
+    usage
+}
+
+/// Criteria for [`find`], every criterion that is set has to match.
+#[derive(Debug, Clone, Default)]
+pub struct FindQuery {
+    /// Glob matched against the name of a file or directory, `*` matches any
+    /// run of characters and `?` a single character.
+    pub name: Option<String>,
+    pub min_size: Option<usize>,
+    pub max_size: Option<usize>,
+    pub directories_only: bool,
+    pub files_only: bool,
+}
+
+impl FindQuery {
+    fn matches(&self, payload: &NodePayload) -> bool {
+        let is_directory = matches!(payload, NodePayload::FilesystemNode(_));
+        (!self.directories_only || is_directory) &&
+            (!self.files_only || !is_directory) &&
+            self.min_size.is_none_or(|min_size| payload.size() >= min_size) &&
+            self.max_size.is_none_or(|max_size| payload.size() <= max_size) &&
+            self.name.as_ref().is_none_or(|pattern| glob_match(pattern, payload.name()))
+    }
+}
+
+/// Files and directories matching `query`, ordered by path.
+pub fn find(filesystem: &Filesystem, query: &FindQuery) -> Vec<usize> {
+    let mut found: Vec<(String, usize)> = filesystem
+        .nodes()
+        .into_iter()
+        .filter(|node_id| filesystem.with_payload(*node_id, |payload| query.matches(payload)).unwrap_or(false))
+        .map(|node_id| (filesystem.path_of(node_id), node_id))
+        .collect();
+    found.sort();
+    found
+        .into_iter()
+        .map(|(_, node_id)| node_id)
+        .collect()
+}
+
+/// Match `name` against a glob of `*` and `?` wildcards.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Where to resume after the last `*` when the characters stop matching.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ =>
+                match backtrack {
+                    Some((star, matched)) => {
+                        p = star + 1;
+                        n = matched + 1;
+                        backtrack = Some((star, matched + 1));
+                    }
+                    None => {
+                        return false;
+                    }
+                }
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Render the tree the way the puzzle does, with the size of every file and
+/// directory.
+///
+/// ```text
+/// - / (dir, size=48381165)
+///   - a (dir, size=94853)
+///     - e (dir, size=584)
+///       - i (file, size=584)
+/// ```
+pub fn render_tree(filesystem: &Filesystem) -> String {
+    let mut rendered = String::new();
+    render_node(filesystem, filesystem.root(), 0, &mut rendered);
+    rendered
+}
+
+fn render_node(filesystem: &Filesystem, node_id: usize, depth: usize, rendered: &mut String) {
+    let indent = "  ".repeat(depth);
+    match filesystem.payload(node_id) {
+        Some(NodePayload::FilesystemLeaf(file)) => {
+            let _ = writeln!(rendered, "{}- {} (file, size={})", indent, file.name(), file.size());
+        }
+        Some(NodePayload::FilesystemNode(directory)) => {
+            let name = if node_id == filesystem.root() { "/" } else { directory.name() };
+            let _ = writeln!(rendered, "{}- {} (dir, size={})", indent, name, directory.size());
+            for child in filesystem.children_by_name(node_id) {
+                render_node(filesystem, child, depth + 1, rendered);
+            }
+        }
+        None => (),
+    }
+}
+
+/// The smallest single directory holding at least `bytes`, the puzzle's part 2.
+pub fn smallest_directory_to_free(filesystem: &Filesystem, bytes: usize) -> Option<usize> {
+    filesystem
+        .directories()
+        .into_iter()
+        .filter(|directory| filesystem.size_of(*directory) >= bytes)
+        .min_by_key(|directory| filesystem.size_of(*directory))
+}
+
+/// Steps [`directories_to_free`] searches for before settling for the best set
+/// of directories found so far.
+pub const DELETION_SEARCH_STEPS: usize = 200_000;
+
+/// The set of directories that frees at least `bytes` while deleting as little
+/// as possible, preferring fewer directories on a tie. Directories in the set
+/// never contain one another. `None` when even deleting everything isn't
+/// enough.
+///
+/// This is a branch and bound search, only directories smaller than the best
+/// single directory are combined. The search is exponential in the number of
+/// those directories, so it stops after [`DELETION_SEARCH_STEPS`] steps with
+/// the best set found by then, which is never worse than the best single
+/// directory.
+pub fn directories_to_free(filesystem: &Filesystem, bytes: usize) -> Option<Vec<usize>> {
+    let best_single = smallest_directory_to_free(filesystem, bytes)?;
+    if bytes == 0 {
+        return Some(Vec::new());
+    }
+    let best_size = filesystem.size_of(best_single);
+
+    let mut spans = HashMap::new();
+    number_directories(filesystem, filesystem.root(), &mut 0, &mut spans);
+    let mut candidates: Vec<(usize, usize, Span)> = spans
+        .iter()
+        .map(|(directory, span)| (filesystem.size_of(*directory), *directory, *span))
+        .filter(|(size, _, _)| *size > 0 && *size < best_size)
+        .collect();
+    candidates.sort_by(|a, b| b.cmp(a));
+    // Sizes still available from each candidate onwards, an upper bound as
+    // nested directories are counted twice.
+    let mut remaining = vec![0; candidates.len() + 1];
+    for index in (0..candidates.len()).rev() {
+        remaining[index] = remaining[index + 1] + candidates[index].0;
+    }
+    let mut search = DeletionSearch {
+        bytes,
+        best_size,
+        best: vec![best_single],
+        candidates,
+        remaining,
+        steps_left: DELETION_SEARCH_STEPS,
+    };
+    search.search(0, 0, &mut Vec::new());
+
+    Some(search.best)
+}
+
+/// Where a directory starts and ends in a depth first numbering of the
+/// directories, a directory is inside another when its span is.
+type Span = (usize, usize);
+
+fn number_directories(filesystem: &Filesystem, directory: usize, next: &mut usize, spans: &mut HashMap<usize, Span>) {
+    let start = *next;
+    *next += 1;
+    let sub_directories: Vec<usize> = filesystem
+        .with_directory(directory, |directory| directory.directories().collect())
+        .unwrap_or_default();
+    for sub_directory in sub_directories {
+        number_directories(filesystem, sub_directory, next, spans);
+    }
+    spans.insert(directory, (start, *next));
+}
+
+struct DeletionSearch {
+    bytes: usize,
+    best_size: usize,
+    best: Vec<usize>,
+    /// Directories to combine as `(size, id, span)`, largest first.
+    candidates: Vec<(usize, usize, Span)>,
+    remaining: Vec<usize>,
+    steps_left: usize,
+}
+
+impl DeletionSearch {
+    fn search(&mut self, index: usize, size: usize, chosen: &mut Vec<usize>) {
+        if self.steps_left == 0 {
+            return;
+        }
+        self.steps_left -= 1;
+
+        if size >= self.bytes {
+            if size < self.best_size || (size == self.best_size && chosen.len() < self.best.len()) {
+                self.best_size = size;
+                self.best = chosen
+                    .iter()
+                    .map(|chosen_index| self.candidates[*chosen_index].1)
+                    .collect();
+            }
+            return;
+        }
+        if index == self.candidates.len() || size + self.remaining[index] < self.bytes {
+            return;
+        }
+
+        let candidate_size = self.candidates[index].0;
+        if size + candidate_size <= self.best_size && !self.overlaps(index, chosen) {
+            chosen.push(index);
+            self.search(index + 1, size + candidate_size, chosen);
+            chosen.pop();
+        }
+        self.search(index + 1, size, chosen);
+    }
+
+    /// Whether the candidate at `index` is inside one of the `chosen`
+    /// candidates or the other way round.
+    fn overlaps(&self, index: usize, chosen: &[usize]) -> bool {
+        let (start, end) = self.candidates[index].2;
+        chosen.iter().any(|chosen_index| {
+            let (chosen_start, chosen_end) = self.candidates[*chosen_index].2;
+            (chosen_start <= start && end <= chosen_end) || (start <= chosen_start && chosen_end <= end)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoSpaceLeftOnDevice;
+    use aoc_common_lib::solution::Solution;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    fn paths(filesystem: &Filesystem, node_ids: &[usize]) -> Vec<String> {
+        node_ids
+            .iter()
+            .map(|node_id| filesystem.path_of(*node_id))
+            .collect()
+    }
+
+    #[test]
+    fn queries_example_filesystem() {
+        let filesystem = NoSpaceLeftOnDevice.parse(EXAMPLE).unwrap();
+        assert_eq!(disk_usage(&filesystem), vec![
+            (String::from("/"), 48381165),
+            (String::from("/a/"), 94853),
+            (String::from("/a/e/"), 584),
+            (String::from("/d/"), 24933642)
+        ]);
+
+        let query = FindQuery { name: Some(String::from("*.???")), min_size: Some(8_000_000), ..FindQuery::default() };
+        assert_eq!(paths(&filesystem, &find(&filesystem, &query)), vec!["/b.txt", "/c.dat", "/d/d.log"]);
+        let query = FindQuery { max_size: Some(100_000), directories_only: true, ..FindQuery::default() };
+        assert_eq!(paths(&filesystem, &find(&filesystem, &query)), vec!["/a/", "/a/e/"]);
+
+        let rendered = render_tree(&filesystem);
+        assert!(rendered.starts_with("- / (dir, size=48381165)\n  - a (dir, size=94853)\n    - e (dir, size=584)\n"));
+        assert!(rendered.ends_with("    - j (file, size=4060174)\n    - k (file, size=7214296)\n"));
+    }
+
+    #[test]
+    fn combines_directories_to_free_space() {
+        let mut filesystem = Filesystem::new();
+        let root = filesystem.root();
+        for (name, size) in [("a", 60), ("b", 45), ("c", 40)] {
+            let directory = filesystem.add_directory(root, name);
+            filesystem.add_file(directory, "file", size);
+        }
+        let single = smallest_directory_to_free(&filesystem, 80).unwrap();
+        assert_eq!(filesystem.path_of(single), "/");
+        assert_eq!(paths(&filesystem, &directories_to_free(&filesystem, 80).unwrap()), vec!["/b/", "/c/"]);
+        assert_eq!(paths(&filesystem, &directories_to_free(&filesystem, 50).unwrap()), vec!["/a/"]);
+        assert_eq!(directories_to_free(&filesystem, 1000), None);
+    }
+
+    #[test]
+    fn frees_space_on_puzzle_sized_trees() {
+        // About 180 directories a few levels deep with sizes from a fixed
+        // sequence, like a real puzzle input
+        let mut filesystem = Filesystem::new();
+        let mut directories = vec![filesystem.root()];
+        let mut seed: usize = 7;
+        for index in 0..180 {
+            seed = (seed * 1_103_515_245 + 12_345) % (1 << 31);
+            let parent = directories[seed % directories.len().min(index / 4 + 1)];
+            let directory = filesystem.add_directory(parent, &format!("d{}", index));
+            filesystem.add_file(directory, "file", seed % 300_000);
+            directories.push(directory);
+        }
+
+        let bytes = 5_000_000;
+        let single = smallest_directory_to_free(&filesystem, bytes).unwrap();
+        let chosen = directories_to_free(&filesystem, bytes).unwrap();
+        let freed: usize = chosen
+            .iter()
+            .map(|directory| filesystem.size_of(*directory))
+            .sum();
+        assert!(freed >= bytes && freed <= filesystem.size_of(single));
+        for directory in chosen.iter() {
+            assert!(filesystem.ancestors_of(*directory).iter().all(|ancestor| !chosen.contains(ancestor)));
+        }
+    }
+
+    #[test]
+    fn matches_globs() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*.log", "d.log"));
+        assert!(glob_match("d.*", "d.ext"));
+        assert!(glob_match("?", "k"));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("*.log", "d.ext"));
+        assert!(!glob_match("?", "ab"));
+    }
+}
@@ -0,0 +1,276 @@
+//! Consistency checks for a terminal session. Replaying assumes the transcript
+//! is well formed, these checks point out the lines where it isn't so that a
+//! wrong tree isn't built silently.
+
+use std::{ collections::{ BTreeMap, HashMap }, fmt };
+
+use aoc_common_lib::diagnostic::Location;
+
+use crate::model::Filesystem;
+use crate::parser::{ ShellLine, TranscriptLine };
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptIssue {
+    /// `cd` into a directory that no `ls` has listed.
+    UnlistedDirectory {
+        name: String,
+    },
+    /// `ls` of a directory whose contents differ from an earlier `ls` of it.
+    ConflictingListing {
+        path: String,
+        first_line: usize,
+    },
+    /// A file listed again with a different size.
+    ConflictingFileSize {
+        path: String,
+        size: usize,
+        first_size: usize,
+        first_line: usize,
+    },
+    /// `cd ..` while already at the root directory.
+    AboveRoot,
+}
+
+impl fmt::Display for TranscriptIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnlistedDirectory { name } => write!(f, "cd into `{}` which has not been listed by ls", name),
+            Self::ConflictingListing { path, first_line } =>
+                write!(f, "ls of {} differs from its listing on line {}", path, first_line),
+            Self::ConflictingFileSize { path, size, first_size, first_line } =>
+                write!(f, "{} is listed with size {} but had size {} on line {}", path, size, first_size, first_line),
+            Self::AboveRoot => write!(f, "cd .. while already at the root directory"),
+        }
+    }
+}
+
+/// A [`TranscriptIssue`] and the line it was found on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptWarning {
+    pub issue: TranscriptIssue,
+    pub location: Location,
+}
+
+impl TranscriptWarning {
+    pub fn line_number(&self) -> usize {
+        self.location.line
+    }
+}
+
+impl fmt::Display for TranscriptWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "warning: {}\n{}", self.issue, self.location)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ListedEntry {
+    Directory,
+    File(usize),
+}
+
+/// The contents of one directory as printed by a single `ls`.
+struct Listing {
+    directory: usize,
+    line_number: usize,
+    text: String,
+    entries: BTreeMap<String, ListedEntry>,
+}
+
+struct Validator {
+    filesystem: Filesystem,
+    current_directory: usize,
+    listing: Option<Listing>,
+    /// The first complete listing of each directory and its line number.
+    listings: HashMap<usize, (usize, BTreeMap<String, ListedEntry>)>,
+    /// Line each file was first listed on.
+    file_lines: HashMap<usize, usize>,
+    warnings: Vec<TranscriptWarning>,
+}
+
+impl Validator {
+    fn new() -> Self {
+        let filesystem = Filesystem::new();
+        Self {
+            current_directory: filesystem.root(),
+            filesystem,
+            listing: None,
+            listings: HashMap::new(),
+            file_lines: HashMap::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn warn(&mut self, issue: TranscriptIssue, location: Location) {
+        self.warnings.push(TranscriptWarning { issue, location });
+    }
+
+    fn finish_listing(&mut self) {
+        let listing = match self.listing.take() {
+            Some(listing) => listing,
+            None => {
+                return;
+            }
+        };
+        match self.listings.get(&listing.directory) {
+            Some((first_line, entries)) if *entries != listing.entries => {
+                let issue = TranscriptIssue::ConflictingListing {
+                    path: self.filesystem.path_of(listing.directory),
+                    first_line: *first_line,
+                };
+                self.warn(issue, Location::line(listing.line_number, &listing.text));
+            }
+            Some(_) => (),
+            None => {
+                self.listings.insert(listing.directory, (listing.line_number, listing.entries));
+            }
+        }
+    }
+
+    fn change_directory(&mut self, line: &TranscriptLine, path: &str) {
+        // The path is the end of the line, underline all of it
+        let location = match line.text.rfind(path) {
+            Some(offset) => Location::token(line.line_number, line.text, &line.text[offset..offset + path.len()]),
+            None => Location::line(line.line_number, line.text),
+        };
+        if path.starts_with('/') {
+            self.current_directory = self.filesystem.root();
+        }
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            match name {
+                "." => (),
+                ".." =>
+                    match self.filesystem.parent_of(self.current_directory) {
+                        Some(parent) => {
+                            self.current_directory = parent;
+                        }
+                        None => self.warn(TranscriptIssue::AboveRoot, location.clone()),
+                    }
+                name =>
+                    match self.filesystem.change_directory(self.current_directory, name) {
+                        Some(directory) => {
+                            self.current_directory = directory;
+                        }
+                        None => {
+                            let issue = TranscriptIssue::UnlistedDirectory { name: String::from(name) };
+                            self.warn(issue, location.clone());
+                            self.current_directory = self.filesystem.add_directory(self.current_directory, name);
+                        }
+                    }
+            }
+        }
+    }
+
+    fn list(&mut self, line: &TranscriptLine, name: &str, entry: ListedEntry) {
+        if let Some(listing) = &mut self.listing {
+            listing.entries.insert(String::from(name), entry.clone());
+        }
+        let size = match entry {
+            ListedEntry::Directory => {
+                self.filesystem.add_directory(self.current_directory, name);
+                return;
+            }
+            ListedEntry::File(size) => size,
+        };
+
+        let existing = self.filesystem
+            .with_directory(self.current_directory, |directory| directory.file(name))
+            .flatten();
+        match existing {
+            Some(file) if self.filesystem.size_of(file) != size => {
+                let issue = TranscriptIssue::ConflictingFileSize {
+                    path: self.filesystem.path_of(file),
+                    size,
+                    first_size: self.filesystem.size_of(file),
+                    first_line: self.file_lines.get(&file).copied().unwrap_or_default(),
+                };
+                self.warn(issue, Location::line(line.line_number, line.text));
+            }
+            Some(_) => (),
+            None => {
+                let file = self.filesystem.add_file(self.current_directory, name, size);
+                self.file_lines.insert(file, line.line_number);
+            }
+        }
+    }
+
+    fn check(&mut self, line: &TranscriptLine) {
+        match &line.shell_line {
+            ShellLine::ShellCommand(shell_command) => {
+                self.finish_listing();
+                match (shell_command.command(), shell_command.args()) {
+                    ("ls", _) => {
+                        self.listing = Some(Listing {
+                            directory: self.current_directory,
+                            line_number: line.line_number,
+                            text: String::from(line.text),
+                            entries: BTreeMap::new(),
+                        });
+                    }
+                    ("cd", [path]) => self.change_directory(line, path),
+                    _ => (),
+                }
+            }
+            ShellLine::ShellDirectory(shell_directory) => {
+                self.list(line, shell_directory.name(), ListedEntry::Directory);
+            }
+            ShellLine::ShellFile(shell_file) => {
+                self.list(line, shell_file.name(), ListedEntry::File(shell_file.size()));
+            }
+        }
+    }
+}
+
+/// Replay `transcript` and report every inconsistency found, in line order.
+pub fn validate_transcript(transcript: &[TranscriptLine]) -> Vec<TranscriptWarning> {
+    let mut validator = Validator::new();
+    for line in transcript.iter() {
+        validator.check(line);
+    }
+    validator.finish_listing();
+    let mut warnings = validator.warnings;
+    warnings.sort_by_key(|warning| warning.line_number());
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_transcript;
+
+    const EXAMPLE: &str = include_str!("../../input_advent_example.txt");
+
+    fn issues(input: &str) -> Vec<(usize, TranscriptIssue)> {
+        validate_transcript(&parse_transcript(input).unwrap())
+            .into_iter()
+            .map(|warning| (warning.line_number(), warning.issue))
+            .collect()
+    }
+
+    #[test]
+    fn example_is_consistent() {
+        assert_eq!(issues(EXAMPLE), vec![]);
+    }
+
+    #[test]
+    fn flags_inconsistent_transcripts() {
+        let input = "$ cd /\n$ ls\ndir a\n10 b.txt\n$ cd x\n$ cd /\n$ ls\ndir a\n12 b.txt\n$ cd ..\n$ cd a/../..";
+        assert_eq!(issues(input), vec![
+            (5, TranscriptIssue::UnlistedDirectory { name: String::from("x") }),
+            (7, TranscriptIssue::ConflictingListing { path: String::from("/"), first_line: 2 }),
+            (9, TranscriptIssue::ConflictingFileSize {
+                path: String::from("/b.txt"),
+                size: 12,
+                first_size: 10,
+                first_line: 4,
+            }),
+            (10, TranscriptIssue::AboveRoot),
+            (11, TranscriptIssue::AboveRoot)
+        ]);
+
+        let warnings = validate_transcript(&parse_transcript("$ cd /\n$ cd ..").unwrap());
+        let expected =
+            "warning: cd .. while already at the root directory\n --> <input>:2:6\n  |\n2 | $ cd ..\n  |      ^^";
+        assert_eq!(format!("{}", warnings[0]), expected);
+    }
+}
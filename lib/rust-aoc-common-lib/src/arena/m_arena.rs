@@ -49,28 +49,26 @@ impl<T> HasId for Node<T> where T: Debug + Clone + Send + Sync {
 /// 1. [Wikipedia definition of memory
 ///    arena](https://en.wikipedia.org/wiki/Region-based_memory_management)
 /// 2. You can learn more about how this library was built from this
-/// [developerlife.com    article](https://developerlife.com/2022/02/24/rust-non-binary-tree/).
+///    [developerlife.com article](https://developerlife.com/2022/02/24/rust-non-binary-tree/).
 ///
 /// # Examples
 ///
 /// ## Basic usage
 ///
 /// ```rust
-/// use r3bl_rs_utils::tree_memory_arena::{Arena, HasId, MTArena, ResultUidList};
-/// use r3bl_rs_utils_core::{style_primary, style_prompt};
+/// use aoc_common_lib::arena::{Arena, HasId, MTArena, ResultUidList};
 ///
 /// let mut arena = Arena::<usize>::new();
 /// let node_1_value = 42 as usize;
 /// let node_1_id = arena.add_new_node(node_1_value, None);
-/// println!("{} {:#?}", style_primary("node_1_id"), node_1_id);
+/// println!("{} {:#?}", "node_1_id", node_1_id);
 /// assert_eq!(node_1_id, 0);
 /// ```
 ///
 /// ## Get weak and strong references from the arena (tree), and tree walking
 ///
 /// ```rust
-/// use r3bl_rs_utils::tree_memory_arena::{Arena, HasId, MTArena, ResultUidList};
-/// use r3bl_rs_utils_core::{style_primary, style_prompt};
+/// use aoc_common_lib::arena::{Arena, HasId, MTArena, ResultUidList};
 ///
 /// let mut arena = Arena::<usize>::new();
 /// let node_1_value = 42 as usize;
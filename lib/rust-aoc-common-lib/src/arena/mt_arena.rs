@@ -29,7 +29,7 @@ use crate::utility::ReadGuarded;
 /// 1. [Wikipedia definition of memory
 ///    arena](https://en.wikipedia.org/wiki/Region-based_memory_management)
 /// 2. You can learn more about how this library was built from this
-/// [developerlife.com    article](https://developerlife.com/2022/02/24/rust-non-binary-tree/).
+///    [developerlife.com article](https://developerlife.com/2022/02/24/rust-non-binary-tree/).
 ///
 /// # Examples
 ///
@@ -37,8 +37,7 @@ use crate::utility::ReadGuarded;
 /// use std::{sync::Arc,
 ///           thread::{self, JoinHandle}};
 ///
-/// use r3bl_rs_utils::tree_memory_arena::{Arena, HasId, MTArena, ResultUidList};
-/// use r3bl_rs_utils_core::{style_primary, style_prompt};
+/// use aoc_common_lib::arena::{Arena, HasId, MTArena, ResultUidList};
 ///
 /// type ThreadResult = Vec<usize>;
 /// type Handles = Vec<JoinHandle<ThreadResult>>;
@@ -63,7 +62,7 @@ use crate::utility::ReadGuarded;
 ///   let fn_arc = Arc::new(move |uid, payload| {
 ///     println!(
 ///       "{} {} {} Arena weak_count:{} strong_count:{}",
-///       style_primary("walker_fn - closure"),
+///       "walker_fn - closure",
 ///       uid,
 ///       payload,
 ///       Arc::weak_count(&arena_arc),
@@ -0,0 +1,78 @@
+//! Rustc style rendering of a location in a puzzle input, e.g.
+//!
+//! ```text
+//!  --> day-04/input_advent_example.txt:3:3
+//!   |
+//! 3 | 5-x,7-9
+//!   |   ^
+//! ```
+
+use std::{ fmt, path::{ Path, PathBuf } };
+
+/// A span within a single line of a puzzle input. Lines and columns are
+/// 1-based and counted in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    pub text: String,
+}
+
+impl Location {
+    /// Underline `length` characters of `text` starting at `column`.
+    pub fn new(line: usize, text: &str, column: usize, length: usize) -> Self {
+        Self {
+            file: None,
+            line,
+            column: column.max(1),
+            length: length.max(1),
+            text: String::from(text),
+        }
+    }
+
+    /// Underline the whole of a line.
+    pub fn line(line: usize, text: &str) -> Self {
+        Self::new(line, text, 1, text.chars().count())
+    }
+
+    /// Underline `token`, which should be a slice of `text`. When it isn't, the
+    /// first occurrence of `token` is used, falling back to the whole line.
+    pub fn token(line: usize, text: &str, token: &str) -> Self {
+        match byte_offset_of(text, token) {
+            Some(offset) => Self::new(line, text, text[..offset].chars().count() + 1, token.chars().count()),
+            None => Self::line(line, text),
+        }
+    }
+
+    pub fn in_file(mut self, file: &Path) -> Self {
+        self.file = Some(file.to_path_buf());
+        self
+    }
+}
+
+fn byte_offset_of(text: &str, token: &str) -> Option<usize> {
+    let text_start = text.as_ptr() as usize;
+    let token_start = token.as_ptr() as usize;
+    if token_start >= text_start && token_start + token.len() <= text_start + text.len() {
+        Some(token_start - text_start)
+    } else {
+        text.find(token)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let file = match &self.file {
+            Some(file) => file.display().to_string(),
+            None => String::from("<input>"),
+        };
+        writeln!(f, "{}--> {}:{}:{}", gutter, file, self.line, self.column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", line_number, self.text)?;
+        write!(f, "{} | {}{}", gutter, " ".repeat(self.column - 1), "^".repeat(self.length))
+    }
+}
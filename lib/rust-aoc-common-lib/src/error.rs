@@ -1,20 +1,305 @@
-use std::{error::Error, fmt};
+//! [`AocError`] is the error type shared by the library, the runner and every
+//! day. Parse failures carry the line, column and offending text of the input
+//! so that a bad puzzle input can be tracked down without a debugger.
 
-#[derive(Debug, Clone)]
-pub struct RuntimeError {
-    message: String,
+use std::{ error::Error, fmt, io, num::ParseIntError, path::{ Path, PathBuf } };
+
+use pest::{ error::LineColLocation, RuleType };
+
+use crate::diagnostic::Location;
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+type BoxedSource = Box<dyn Error + Send + Sync + 'static>;
+
+pub enum AocError {
+    /// The command line was missing a required argument or had a malformed one.
+    Usage(String),
+    /// The input file does not exist.
+    MissingInput(PathBuf),
+    /// Reading the input failed.
+    Io(io::Error),
+    /// The input could not be parsed.
+    Parse(ParseError),
+    /// A command in the input can't be carried out, e.g. a crane move
+    /// referencing a stack that doesn't exist.
+    InvalidCommand {
+        command: String,
+        reason: String,
+        location: Option<Location>,
+    },
+    /// The input parsed, but parts of it contradict each other.
+    Inconsistent(String),
+    /// The input parsed, but there is no answer for it.
+    Unsolvable(String),
+}
+
+impl AocError {
+    pub fn usage(message: &str) -> Self {
+        Self::Usage(String::from(message))
+    }
+
+    pub fn invalid_command(command: impl fmt::Display, reason: &str) -> Self {
+        Self::InvalidCommand {
+            command: command.to_string(),
+            reason: String::from(reason),
+            location: None,
+        }
+    }
+
+    pub fn inconsistent(message: &str) -> Self {
+        Self::Inconsistent(String::from(message))
+    }
+
+    pub fn unsolvable(message: &str) -> Self {
+        Self::Unsolvable(String::from(message))
+    }
+
+    /// Attach the 1-based line number and the text of that line to a parse
+    /// error or an invalid command. Any column already known is kept, other
+    /// variants are returned untouched.
+    pub fn on_line(self, line: usize, text: &str) -> Self {
+        match self {
+            Self::Parse(parse_error) => Self::Parse(parse_error.on_line(line, text)),
+            Self::InvalidCommand { command, reason, location } => {
+                let location = match location {
+                    Some(location) => Location { line, text: String::from(text), ..location },
+                    None => Location::line(line, text),
+                };
+                Self::InvalidCommand { command, reason, location: Some(location) }
+            }
+            other => other,
+        }
+    }
+
+    /// Locate a parse error or an invalid command at `token`, a slice of the
+    /// line `text`.
+    pub fn at_token(self, line: usize, text: &str, token: &str) -> Self {
+        match self {
+            Self::Parse(parse_error) => Self::Parse(parse_error.located(Location::token(line, text, token))),
+            Self::InvalidCommand { command, reason, .. } =>
+                Self::InvalidCommand { command, reason, location: Some(Location::token(line, text, token)) },
+            other => other,
+        }
+    }
+
+    /// Name the input file a located error came from.
+    pub fn in_file(self, file: &Path) -> Self {
+        match self {
+            Self::Parse(parse_error) => Self::Parse(parse_error.in_file(file)),
+            Self::InvalidCommand { command, reason, location } =>
+                Self::InvalidCommand { command, reason, location: location.map(|location| location.in_file(file)) },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Usage(message) => write!(f, "{}", message),
+            Self::MissingInput(path) => write!(f, "Path {} does not appear to exist", path.display()),
+            Self::Io(err) => write!(f, "Failed to read input: {}", err),
+            Self::Parse(parse_error) => write!(f, "{}", parse_error),
+            Self::InvalidCommand { command, reason, location } => {
+                write!(f, "Invalid command `{}`: {}", command, reason)?;
+                if let Some(location) = location {
+                    write!(f, "\n{}", location)?;
+                }
+                Ok(())
+            }
+            Self::Inconsistent(message) => write!(f, "Inconsistent input: {}", message),
+            Self::Unsolvable(message) => write!(f, "Unsolvable input: {}", message),
+        }
+    }
+}
+
+/// `main` prints the `Debug` form of a returned error, so this writes the
+/// readable message followed by the chain of underlying causes.
+impl fmt::Debug for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)?;
+        let mut source = self.source();
+        while let Some(cause) = source {
+            write!(f, "\n  caused by: {}", cause)?;
+            source = cause.source();
+        }
+        Ok(())
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(parse_error) => parse_error.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseError> for AocError {
+    fn from(parse_error: ParseError) -> Self {
+        Self::Parse(parse_error)
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(err: ParseIntError) -> Self {
+        Self::Parse(ParseError::new("expected a number").with_source(err))
+    }
+}
+
+/// The pest error is fully described by its location, line and message, so it
+/// isn't kept as a source.
+impl<R> From<pest::error::Error<R>> for AocError where R: RuleType {
+    fn from(err: pest::error::Error<R>) -> Self {
+        let (line, column, length) = match err.line_col {
+            LineColLocation::Pos((line, column)) => (line, column, 1),
+            LineColLocation::Span((line, column), (end_line, end_column)) if line == end_line => {
+                (line, column, end_column.saturating_sub(column))
+            }
+            LineColLocation::Span((line, column), _) => (line, column, 1),
+        };
+        let location = Location::new(line, err.line(), column, length);
+        Self::Parse(ParseError::new(&err.variant.message()).located(location))
+    }
 }
 
-impl fmt::Display for RuntimeError {
+/// A parse failure, optionally located within the input. Located errors are
+/// displayed with a rustc style snippet of the offending line.
+#[derive(Debug)]
+pub struct ParseError {
+    pub message: String,
+    pub location: Option<Location>,
+    source: Option<BoxedSource>,
+}
+
+impl ParseError {
+    pub fn new(message: &str) -> Self {
+        Self {
+            message: String::from(message),
+            location: None,
+            source: None,
+        }
+    }
+
+    pub fn located(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    pub fn with_source<E>(mut self, source: E) -> Self where E: Error + Send + Sync + 'static {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    /// Parsers that work a line at a time report line 1, so the real line
+    /// number replaces it while a known column is kept.
+    fn on_line(mut self, line: usize, text: &str) -> Self {
+        let location = match self.location.take() {
+            Some(location) => Location { line, text: String::from(text), ..location },
+            None => Location::line(line, text),
+        };
+        self.located(location)
+    }
+
+    fn in_file(mut self, file: &Path) -> Self {
+        self.location = self.location.map(|location| location.in_file(file));
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.message)
+        write!(f, "{}", self.message)?;
+        if let Some(location) = &self.location {
+            write!(f, "\n{}", location)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_ref().map(|source| source.as_ref() as &(dyn Error + 'static))
     }
 }
 
-impl Error for RuntimeError {}
+/// Attach where in the input a `Result` came from to its parse error.
+pub trait OnLine<T> {
+    fn on_line(self, line: usize, text: &str) -> Result<T>;
+
+    fn at_token(self, line: usize, text: &str, token: &str) -> Result<T>;
+
+    fn in_file(self, file: &Path) -> Result<T>;
+}
+
+impl<T, E> OnLine<T> for std::result::Result<T, E> where E: Into<AocError> {
+    fn on_line(self, line: usize, text: &str) -> Result<T> {
+        self.map_err(|err| err.into().on_line(line, text))
+    }
+
+    fn at_token(self, line: usize, text: &str, token: &str) -> Result<T> {
+        self.map_err(|err| err.into().at_token(line, text, token))
+    }
+
+    fn in_file(self, file: &Path) -> Result<T> {
+        self.map_err(|err| err.into().in_file(file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_int_error_is_located_on_its_line() {
+        let err = "12a".parse::<u32>().on_line(3, "12a").unwrap_err();
+        match &err {
+            AocError::Parse(parse_error) => {
+                assert_eq!(parse_error.location, Some(Location::line(3, "12a")));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn located_errors_render_a_snippet() {
+        let line = "2-4,x-8";
+        let err = "x".parse::<u32>().at_token(12, line, &line[4..5]).in_file(Path::new("input.txt")).unwrap_err();
+        assert_eq!(
+            format!("{}", err),
+            ["expected a number", "  --> input.txt:12:5", "   |", "12 | 2-4,x-8", "   |     ^"].join("\n")
+        );
+    }
+
+    #[test]
+    fn invalid_commands_can_be_located() {
+        let command = "move 5 from 1 to 2";
+        let err = AocError::invalid_command(command, "stack 1 holds 3 crates").on_line(7, command);
+        assert_eq!(
+            format!("{}", err),
+            [
+                "Invalid command `move 5 from 1 to 2`: stack 1 holds 3 crates",
+                " --> <input>:7:1",
+                "  |",
+                "7 | move 5 from 1 to 2",
+                "  | ^^^^^^^^^^^^^^^^^^",
+            ].join("\n")
+        );
+    }
 
-impl RuntimeError {
-    pub fn new(message: String) -> Self {
-        Self { message }
+    #[test]
+    fn other_errors_ignore_line_information() {
+        let err = AocError::unsolvable("no answer").on_line(1, "text");
+        assert_eq!(format!("{}", err), "Unsolvable input: no answer");
     }
 }
@@ -0,0 +1,227 @@
+//! [`Input`] loads a puzzle input once and hands out the views the days need:
+//! lines, blank line separated blocks, raw bytes or the whole text.
+//!
+//! Inputs can come from a file, from stdin (a path of `-`), from a gzip
+//! compressed `.gz` file or straight from a string, which is handy in tests.
+//! Files of [`MMAP_THRESHOLD`] bytes or more are memory mapped rather than read
+//! into memory. [`InputReader`] reads the same sources a line at a time instead.
+
+use std::{ fs::File, io::{ self, BufRead, BufReader, Read }, path::{ Path, PathBuf }, str };
+
+use flate2::read::GzDecoder;
+use memmap2::Mmap;
+
+use crate::error::{ AocError, ParseError, Result };
+use crate::utility::{ blocks, Blocks };
+
+/// Files at least this large are memory mapped by [`Input::open`].
+pub const MMAP_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+const STDIN_PATH: &str = "-";
+
+enum Contents {
+    Text(String),
+    Mapped(Mmap),
+}
+
+pub struct Input {
+    name: PathBuf,
+    contents: Contents,
+}
+
+impl Input {
+    /// Open `path`, reading stdin for `-` and decompressing `.gz` files.
+    pub fn open<P>(path: P) -> Result<Self> where P: AsRef<Path> {
+        let path = path.as_ref();
+        if path == Path::new(STDIN_PATH) {
+            return Self::stdin();
+        }
+        if !path.exists() {
+            return Err(AocError::MissingInput(path.to_path_buf()));
+        }
+        if path.extension().is_some_and(|extension| extension == "gz") {
+            return Self::gzip(path);
+        }
+        if path.metadata()?.len() >= MMAP_THRESHOLD {
+            return Self::mapped(path);
+        }
+        let mut text = String::new();
+        File::open(path)?.read_to_string(&mut text)?;
+        Ok(Self::named(path, text))
+    }
+
+    /// Read the whole of stdin.
+    pub fn stdin() -> Result<Self> {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        Ok(Self::named(Path::new("<stdin>"), text))
+    }
+
+    /// Read and decompress a gzip file.
+    pub fn gzip<P>(path: P) -> Result<Self> where P: AsRef<Path> {
+        let path = path.as_ref();
+        let mut text = String::new();
+        GzDecoder::new(File::open(path)?).read_to_string(&mut text)?;
+        Ok(Self::named(path, text))
+    }
+
+    /// Memory map a file instead of reading it, the contents must be UTF-8.
+    pub fn mapped<P>(path: P) -> Result<Self> where P: AsRef<Path> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        // Safety: puzzle inputs aren't expected to be modified while they are
+        // being solved, which is the only way the mapping could change.
+        let mmap = unsafe { Mmap::map(&file)? };
+        if let Err(err) = str::from_utf8(&mmap) {
+            return Err(ParseError::new("input is not valid UTF-8").with_source(err).into());
+        }
+        Ok(Self {
+            name: path.to_path_buf(),
+            contents: Contents::Mapped(mmap),
+        })
+    }
+
+    fn named(name: &Path, text: String) -> Self {
+        Self {
+            name: name.to_path_buf(),
+            contents: Contents::Text(text),
+        }
+    }
+
+    /// The path the input was read from, `<stdin>` or `<input>` for text.
+    pub fn name(&self) -> &Path {
+        &self.name
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.contents {
+            Contents::Text(text) => text,
+            // Checked to be UTF-8 when the file was mapped.
+            Contents::Mapped(mmap) => str::from_utf8(mmap).unwrap_or_default(),
+        }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+
+    pub fn lines(&self) -> str::Lines<'_> {
+        self.as_str().lines()
+    }
+
+    /// Groups of lines separated by blank lines, see [`blocks`].
+    pub fn blocks(&self) -> Blocks<'_> {
+        blocks(self.as_str())
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Self::from(String::from(text))
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Self::named(Path::new("<input>"), text)
+    }
+}
+
+/// A puzzle input that is read as it is used rather than loaded whole, for
+/// inputs too large to keep in memory.
+pub struct InputReader {
+    name: PathBuf,
+    reader: Box<dyn BufRead>,
+}
+
+impl InputReader {
+    /// Open `path` like [`Input::open`] does, without reading it.
+    pub fn open<P>(path: P) -> Result<Self> where P: AsRef<Path> {
+        let path = path.as_ref();
+        if path == Path::new(STDIN_PATH) {
+            return Ok(Self {
+                name: PathBuf::from("<stdin>"),
+                reader: Box::new(io::stdin().lock()),
+            });
+        }
+        if !path.exists() {
+            return Err(AocError::MissingInput(path.to_path_buf()));
+        }
+        let file = File::open(path)?;
+        let reader: Box<dyn BufRead> = if path.extension().is_some_and(|extension| extension == "gz") {
+            Box::new(BufReader::new(GzDecoder::new(file)))
+        } else {
+            Box::new(BufReader::new(file))
+        };
+        Ok(Self {
+            name: path.to_path_buf(),
+            reader,
+        })
+    }
+
+    /// The path the input is read from, `<stdin>` for stdin.
+    pub fn name(&self) -> &Path {
+        &self.name
+    }
+}
+
+impl Read for InputReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl BufRead for InputReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.reader.consume(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{ write::GzEncoder, Compression };
+
+    use super::*;
+
+    #[test]
+    fn splits_text_into_blocks() {
+        let input = Input::from("1\n2\n\n3\n   \n\n4");
+        let blocks: Vec<Vec<&str>> = input.blocks().map(|block| block.lines).collect();
+        assert_eq!(blocks, vec![vec!["1", "2"], vec!["3"], vec!["4"]]);
+        assert_eq!(input.lines().count(), 7);
+        assert_eq!(input.name(), Path::new("<input>"));
+    }
+
+    #[test]
+    fn opens_gzip_and_mapped_files() {
+        let directory = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let gzip_path = directory.join("input.txt.gz");
+        let mut encoder = GzEncoder::new(File::create(&gzip_path).unwrap(), Compression::default());
+        encoder.write_all(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        encoder.finish().unwrap();
+        assert_eq!(Input::open(&gzip_path).unwrap().as_str(), "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+
+        let text_path = directory.join("input.txt");
+        std::fs::write(&text_path, "a\nb\n").unwrap();
+        let mapped = Input::mapped(&text_path).unwrap();
+        assert_eq!(mapped.lines().collect::<Vec<&str>>(), vec!["a", "b"]);
+        assert_eq!(mapped.bytes(), b"a\nb\n");
+
+        let reader = InputReader::open(&gzip_path).unwrap();
+        assert_eq!(reader.name(), gzip_path.as_path());
+        assert_eq!(reader.lines().map(|line| line.unwrap()).collect::<Vec<String>>(), vec![
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb"
+        ]);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert!(matches!(Input::open(&text_path), Err(AocError::MissingInput(_))));
+    }
+}
@@ -1,5 +1,8 @@
 pub mod arena;
+pub mod diagnostic;
 pub mod error;
+pub mod input;
+pub mod solution;
 pub mod tree;
 pub mod utility;
 
@@ -0,0 +1,71 @@
+//! Shared shape for a single day's puzzle so that every day can be driven by
+//! the same runner instead of its own hand rolled `main`.
+//!
+//! A day implements [`Solution`] with its own parsed [`Solution::Input`] and
+//! answer types, which keeps the stages unit testable. The runner only needs
+//! strings in and out, so every [`Solution`] is also usable as a
+//! [`DynSolution`] trait object.
+
+use std::fmt::Display;
+
+use crate::error::Result;
+
+/// A day's puzzle. The raw input is parsed once into [`Solution::Input`], after
+/// which either part can be answered from it.
+pub trait Solution {
+    /// The parsed form of the puzzle input shared by both parts.
+    type Input;
+    /// Answer type of part 1.
+    type Answer1: Display;
+    /// Answer type of part 2.
+    type Answer2: Display;
+
+    /// Parse the raw puzzle input.
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+
+    /// Answer part 1 of the puzzle from the parsed input.
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1>;
+
+    /// Answer part 2 of the puzzle from the parsed input.
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2>;
+}
+
+/// Object safe view of a [`Solution`] so that days with different input and
+/// answer types can be registered side by side.
+pub trait DynSolution {
+    /// Parse the raw puzzle input, returning a handle that can answer both parts.
+    fn parse_input<'a>(&'a self, input: &str) -> Result<Box<dyn ParsedSolution + 'a>>;
+}
+
+/// A [`DynSolution`] that has already parsed its input.
+pub trait ParsedSolution {
+    fn part1(&self) -> Result<String>;
+
+    fn part2(&self) -> Result<String>;
+}
+
+struct Parsed<'a, S> where S: Solution {
+    solution: &'a S,
+    input: S::Input,
+}
+
+impl<'a, S> ParsedSolution for Parsed<'a, S> where S: Solution {
+    fn part1(&self) -> Result<String> {
+        Ok(self.solution.part1(&self.input)?.to_string())
+    }
+
+    fn part2(&self) -> Result<String> {
+        Ok(self.solution.part2(&self.input)?.to_string())
+    }
+}
+
+impl<S> DynSolution for S where S: Solution {
+    fn parse_input<'a>(&'a self, input: &str) -> Result<Box<dyn ParsedSolution + 'a>> {
+        Ok(
+            Box::new(Parsed {
+                solution: self,
+                input: self.parse(input)?,
+            })
+        )
+    }
+}
@@ -0,0 +1,103 @@
+//! Grouping of puzzle input lines into blocks (or paragraphs) separated by
+//! blank lines, e.g. the calorie lists of day 1 or the drawing and move list of
+//! day 5.
+
+use std::{ iter::{ Enumerate, Zip }, ops::RangeFrom, str::Lines, vec };
+
+/// A run of consecutive non-blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// 1-based line number of the first line in the block.
+    pub line_number: usize,
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Block<'a> {
+    /// The lines of the block paired with their 1-based line numbers, ready for
+    /// locating errors.
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        (self.line_number..).zip(self.lines.iter().copied())
+    }
+}
+
+/// Consumes the block into its numbered lines, so `blocks(input).flatten()`
+/// yields every non-blank line with its line number.
+impl<'a> IntoIterator for Block<'a> {
+    type Item = (usize, &'a str);
+    type IntoIter = Zip<RangeFrom<usize>, vec::IntoIter<&'a str>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (self.line_number..).zip(self.lines)
+    }
+}
+
+/// Iterator over the [`Block`]s of an input, see [`blocks`].
+#[derive(Debug, Clone)]
+pub struct Blocks<'a> {
+    lines: Enumerate<Lines<'a>>,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut block: Option<Block<'a>> = None;
+        for (index, line) in self.lines.by_ref() {
+            if line.trim().is_empty() {
+                if block.is_some() {
+                    break;
+                }
+                continue;
+            }
+            block
+                .get_or_insert_with(|| Block {
+                    line_number: index + 1,
+                    lines: Vec::new(),
+                })
+                .lines.push(line);
+        }
+        block
+    }
+}
+
+/// Split `input` into blocks of lines separated by blank lines. Lines holding
+/// only whitespace count as blank, runs of blank lines never yield an empty
+/// block and the last block doesn't need a trailing newline.
+pub fn blocks(input: &str) -> Blocks<'_> {
+    Blocks {
+        lines: input.lines().enumerate(),
+    }
+}
+
+/// Another name for [`blocks`], for inputs that read more like paragraphs.
+pub fn paragraphs(input: &str) -> Blocks<'_> {
+    blocks(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_lines_with_their_line_numbers() {
+        let groups: Vec<Block> = blocks("\n1000\n2000\n\n  \n\n3000\r\n\r\n4000").collect();
+        assert_eq!(groups, vec![
+            Block { line_number: 2, lines: vec!["1000", "2000"] },
+            Block { line_number: 7, lines: vec!["3000"] },
+            Block { line_number: 9, lines: vec!["4000"] }
+        ]);
+        assert_eq!(groups[0].numbered_lines().collect::<Vec<_>>(), vec![(2, "1000"), (3, "2000")]);
+        let line_numbers: Vec<usize> = groups
+            .into_iter()
+            .flatten()
+            .map(|(line_number, _)| line_number)
+            .collect();
+        assert_eq!(line_numbers, vec![2, 3, 7, 9]);
+    }
+
+    #[test]
+    fn blank_input_has_no_blocks() {
+        assert_eq!(blocks("").count(), 0);
+        assert_eq!(paragraphs("\n \n\t\n").count(), 0);
+    }
+}
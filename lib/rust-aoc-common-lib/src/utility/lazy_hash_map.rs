@@ -29,7 +29,7 @@ use std::{ collections::HashMap, hash::Hash };
 /// ```rust
 /// use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
 ///
-/// use r3bl_rs_utils::utils::LazyMemoValues;
+/// use aoc_common_lib::utility::LazyMemoValues;
 ///
 /// // These are copied in the closure below.
 /// let arc_atomic_count = AtomicUsize::new(0);
@@ -20,8 +20,10 @@
 //! 2. Easy to work w/ lazy hash maps.
 //! 3. Easy to work w/ readline.
 //! 4. Interrogation of types.
+//! 5. Splitting puzzle input into blank line separated blocks.
 
 // Attach sources.
+pub mod blocks;
 pub mod file_utils;
 pub mod lazy_field;
 pub mod lazy_hash_map;
@@ -29,6 +31,7 @@ pub mod safe_unwrap;
 pub mod type_utils;
 
 // Re-export.
+pub use blocks::*;
 pub use file_utils::*;
 pub use lazy_field::*;
 pub use lazy_hash_map::*;